@@ -1,11 +1,29 @@
+use std::path::PathBuf;
+
+use clap::Parser;
 use plitedb::{
-    error::PliteDbResult,
-    query::{lexer::tokenize, parser::parse}
+    engine::model::Database,
+    error::{PliteDbError, PliteDbResult},
+    query::{lexer::tokenize, parser::parse_script}
 };
 
+#[derive(Parser)]
+struct Arguments {
+    #[arg(help = "Script of ; separated statements to run before prompting, such as seed data")]
+    script: Option<PathBuf>,
+    #[arg(long, help = "Run the script as a single transaction, so that it applies entirely or not at all")]
+    atomic: bool
+}
+
 fn main() -> PliteDbResult<()> {
+    let arguments = Arguments::parse();
+    let mut database = Database::new(String::from("main"));
     let mut input = String::new();
 
+    if let Some(path) = arguments.script {
+        run(&mut database, &std::fs::read_to_string(path)?, arguments.atomic, false);
+    }
+
     loop {
         input.clear();
 
@@ -17,26 +35,49 @@ fn main() -> PliteDbResult<()> {
             break;
         }
 
-        let tokens = match tokenize(&input) {
-            Ok(tokens) => tokens,
-            Err(e) => {
-                eprintln!("{}", e);
-                continue;
-            }
-        };
+        run(&mut database, &input, false, true);
+    }
 
+    return Ok(());
+}
+
+// Input may hold several statements separated by semicolons, which run in order until one fails.
+fn run(
+    database: &mut Database,
+    input: &str,
+    atomic: bool,
+    verbose: bool
+) {
+    let tokens = match tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    if verbose {
         println!("{:?}", tokens);
+    }
 
-        let ast = match parse(tokens) {
-            Ok(ast) => ast,
-            Err(e) => {
-                eprintln!("{}", e);
-                continue;
-            }
-        };
+    let statements = match parse_script(tokens) {
+        Ok(statements) => statements,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
 
-        println!("{:#?}", ast);
+    if verbose {
+        println!("{:#?}", statements);
     }
 
-    return Ok(());
+    match database.execute_script(statements, atomic) {
+        Ok(outputs) => println!("{:#?}", outputs),
+        Err(PliteDbError::ScriptError(index, outputs, e)) => {
+            println!("{:#?}", outputs);
+            eprintln!("Statement {} failed: {}", index + 1, e);
+        },
+        Err(e) => eprintln!("{}", e)
+    }
 }
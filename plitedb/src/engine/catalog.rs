@@ -0,0 +1,121 @@
+use std::collections::{hash_map::Entry, HashSet};
+
+use super::{
+    error::{EngineError, EngineResult},
+    model::{Database, Store}
+};
+
+impl Database {
+    pub fn store(
+        &self,
+        name: &str
+    ) -> EngineResult<&Store> {
+        return self.store_by_name.get(name).ok_or_else(|| EngineError::StoreNotFound(name.to_string()));
+    }
+
+    pub fn store_mut(
+        &mut self,
+        name: &str
+    ) -> EngineResult<&mut Store> {
+        return self.store_by_name.get_mut(name).ok_or_else(|| EngineError::StoreNotFound(name.to_string()));
+    }
+
+    pub fn create_store(
+        &mut self,
+        store: Store
+    ) -> EngineResult<()> {
+        if let Store::Partition(store) = &store {
+            if store.hash_key_name == store.sort_key_name {
+                return Err(EngineError::ConflictingKeyNames(
+                    store.name.clone(),
+                    store.sort_key_name.clone()
+                ));
+            }
+        }
+
+        // Key attributes are typed by their key definitions, so a schema only covers the others.
+        if let Some(schema) = store.schema() {
+            let key_names = store.key_names();
+            let mut declared = HashSet::with_capacity(schema.attributes.len());
+
+            for attribute in &schema.attributes {
+                if key_names.contains(&&attribute.name) {
+                    return Err(EngineError::SchemaKeyAttribute(
+                        store.name().to_string(),
+                        attribute.name.clone()
+                    ));
+                }
+
+                if !declared.insert(&attribute.name) {
+                    return Err(EngineError::DuplicateSchemaAttribute(
+                        store.name().to_string(),
+                        attribute.name.clone()
+                    ));
+                }
+            }
+        }
+
+        return match self.store_by_name.entry(store.name().to_string()) {
+            Entry::Occupied(entry) => Err(EngineError::StoreAlreadyExists(entry.key().clone())),
+            Entry::Vacant(entry) => {
+                entry.insert(store);
+                Ok(())
+            }
+        };
+    }
+
+    pub fn drop_store(
+        &mut self,
+        name: &str
+    ) -> EngineResult<Store> {
+        return self.store_by_name.remove(name).ok_or_else(|| EngineError::StoreNotFound(name.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        engine::{
+            error::EngineError,
+            executor::QueryOutput,
+            testing::{execute, test_database}
+        },
+        error::PliteDbError
+    };
+
+    #[test]
+    fn creates_and_drops_stores() {
+        let mut database = test_database();
+
+        assert!(matches!(
+            execute(&mut database, "CREATE STORE users HASH KEY id STRING"),
+            Err(PliteDbError::EngineError(EngineError::StoreAlreadyExists(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "CREATE STORE logs HASH KEY id STRING SORT KEY id INTEGER"),
+            Err(PliteDbError::EngineError(EngineError::ConflictingKeyNames(..)))
+        ));
+
+        execute(&mut database, "PUT users { userId: 'a' }").unwrap();
+
+        assert_eq!(
+            execute(&mut database, "DROP STORE users").unwrap(),
+            QueryOutput::StoreDropped(String::from("users"))
+        );
+        assert!(matches!(
+            execute(&mut database, "GET users WHERE { userId = 'a' }"),
+            Err(PliteDbError::EngineError(EngineError::StoreNotFound(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "DROP STORE users"),
+            Err(PliteDbError::EngineError(EngineError::StoreNotFound(_)))
+        ));
+
+        execute(&mut database, "CREATE STORE users HASH KEY userId STRING").unwrap();
+
+        assert_eq!(
+            execute(&mut database, "GET users WHERE { userId = 'a' }").unwrap(),
+            QueryOutput::Item(None)
+        );
+    }
+}
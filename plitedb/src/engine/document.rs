@@ -0,0 +1,316 @@
+use std::collections::{btree_map, hash_map::Entry, BTreeMap, HashMap};
+
+use crate::query::parser::ast::{AttributePath, PathSegment};
+
+use super::{
+    error::{EngineError, EngineResult},
+    model::Value
+};
+
+pub fn get<'a>(
+    attributes: &'a HashMap<String, Value>,
+    path: &AttributePath
+) -> Option<&'a Value> {
+    let mut value = attributes.get(&path.name)?;
+
+    for segment in &path.segments {
+        value = match (segment, value) {
+            (PathSegment::Field(name), Value::Map(map)) => map.get(name)?,
+            (PathSegment::Index(index), Value::List(list)) => list.get(*index)?,
+            _ => return None
+        };
+    }
+
+    return Some(value);
+}
+
+// Every parent along the path must already exist. A field is added to or replaced in its map, and an
+// index past the end of a list appends to it.
+pub fn set(
+    attributes: &mut HashMap<String, Value>,
+    path: &AttributePath,
+    value: Value
+) -> EngineResult<()> {
+    let Some((last, parents)) = path.segments.split_last()
+    else {
+        attributes.insert(path.name.clone(), value);
+
+        return Ok(());
+    };
+
+    let invalid_path = || EngineError::InvalidDocumentPath(path.to_string());
+
+    let mut target = attributes.get_mut(&path.name).ok_or_else(invalid_path)?;
+
+    for segment in parents {
+        target = match (segment, target) {
+            (PathSegment::Field(name), Value::Map(map)) => map.get_mut(name),
+            (PathSegment::Index(index), Value::List(list)) => list.get_mut(*index),
+            _ => None
+        }
+        .ok_or_else(invalid_path)?;
+    }
+
+    match (last, target) {
+        (PathSegment::Field(name), Value::Map(map)) => {
+            map.insert(name.clone(), value);
+        },
+        (PathSegment::Index(index), Value::List(list)) if *index < list.len() => list[*index] = value,
+        (PathSegment::Index(_), Value::List(list)) => list.push(value),
+        _ => return Err(invalid_path())
+    }
+
+    return Ok(());
+}
+
+// Removes the value at the path if it exists. Removing a list element shifts the elements after it.
+pub fn remove(
+    attributes: &mut HashMap<String, Value>,
+    path: &AttributePath
+) -> Option<Value> {
+    let Some((last, parents)) = path.segments.split_last()
+    else {
+        return attributes.remove(&path.name);
+    };
+
+    let mut target = attributes.get_mut(&path.name)?;
+
+    for segment in parents {
+        target = match (segment, target) {
+            (PathSegment::Field(name), Value::Map(map)) => map.get_mut(name)?,
+            (PathSegment::Index(index), Value::List(list)) => list.get_mut(*index)?,
+            _ => return None
+        };
+    }
+
+    return match (last, target) {
+        (PathSegment::Field(name), Value::Map(map)) => map.remove(name),
+        (PathSegment::Index(index), Value::List(list)) if *index < list.len() => Some(list.remove(*index)),
+        _ => None
+    };
+}
+
+// Projected values keep their nesting, so `address.city` projects to `{ address: { city } }`.
+// Elements projected out of a list are compacted into a new list in projection order.
+// Overlapping paths merge: paths into the same list element fill one element, and a path below a
+// value that is already projected whole adds nothing.
+pub fn project(
+    attributes: &HashMap<String, Value>,
+    paths: &[AttributePath]
+) -> HashMap<String, Value> {
+    let mut projected = HashMap::new();
+
+    for path in paths {
+        let Some(value) = get(attributes, path)
+        else {
+            continue;
+        };
+
+        match projected.entry(path.name.clone()) {
+            Entry::Occupied(entry) => merge_projected(entry.into_mut(), &path.segments, value.clone()),
+            Entry::Vacant(entry) => {
+                entry.insert(Projected::new(&path.segments, value.clone()));
+            }
+        }
+    }
+
+    return projected.into_iter().map(|(name, projected)| (name, projected.into_value())).collect();
+}
+
+// The parts of an attribute selected so far. List elements remember the index they were projected
+// from, so that later paths into the same element merge with it.
+enum Projected {
+    Whole(Value),
+    Fields(BTreeMap<String, Projected>),
+    Elements(Vec<(usize, Projected)>)
+}
+
+impl Projected {
+    fn new(
+        segments: &[PathSegment],
+        value: Value
+    ) -> Self {
+        return match segments.split_first() {
+            None => Projected::Whole(value),
+            Some((PathSegment::Field(name), rest)) => Projected::Fields(BTreeMap::from([(name.clone(), Projected::new(rest, value))])),
+            Some((PathSegment::Index(index), rest)) => Projected::Elements(vec![(*index, Projected::new(rest, value))])
+        };
+    }
+
+    fn into_value(self) -> Value {
+        return match self {
+            Projected::Whole(value) => value,
+            Projected::Fields(fields) => Value::Map(fields.into_iter().map(|(name, field)| (name, field.into_value())).collect()),
+            Projected::Elements(elements) => Value::List(elements.into_iter().map(|(_, element)| element.into_value()).collect())
+        };
+    }
+}
+
+fn merge_projected(
+    target: &mut Projected,
+    segments: &[PathSegment],
+    value: Value
+) {
+    let Some((first, rest)) = segments.split_first()
+    else {
+        *target = Projected::Whole(value);
+        return;
+    };
+
+    match (first, target) {
+        (PathSegment::Field(name), Projected::Fields(fields)) => match fields.entry(name.clone()) {
+            btree_map::Entry::Occupied(entry) => merge_projected(entry.into_mut(), rest, value),
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(Projected::new(rest, value));
+            }
+        },
+        (PathSegment::Index(index), Projected::Elements(elements)) => match elements.iter_mut().find(|(source, _)| source == index) {
+            Some((_, element)) => merge_projected(element, rest, value),
+            None => elements.push((*index, Projected::new(rest, value)))
+        },
+        // The whole parent was projected by an earlier path, so this value is already included.
+        _ => ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use crate::{
+        engine::{
+            error::EngineError,
+            evaluator::error::EvaluationError,
+            model::Value,
+            testing::{execute, get_item, scan_page, test_database}
+        },
+        error::PliteDbError
+    };
+
+    #[test]
+    fn projects_requested_attributes() {
+        let mut database = test_database();
+
+        execute(
+            &mut database,
+            "PUT users { userId: 'a', name: 'Alice', age: 30, email: 'a@b.c' }"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "GET users SELECT name, age, missing WHERE { userId = 'a' }");
+
+        assert_eq!(
+            item,
+            HashMap::from([
+                (String::from("name"), Value::String(String::from("Alice"))),
+                (String::from("age"), Value::Integer(30))
+            ])
+        );
+
+        let item = get_item(&mut database, "GET users SELECT userId WHERE { userId = 'a' }");
+
+        assert_eq!(
+            item,
+            HashMap::from([(String::from("userId"), Value::String(String::from("a")))])
+        );
+    }
+
+    #[test]
+    fn reads_and_writes_nested_documents() {
+        let mut database = test_database();
+
+        execute(
+            &mut database,
+            "PUT users { userId: 'a', address: { city: 'Paris', zip: '75001' }, tags: ['admin', 'beta'] }"
+        )
+        .unwrap();
+        execute(&mut database, "PUT users { userId: 'b', address: { city: 'Oslo' }, tags: [] }").unwrap();
+
+        let item = get_item(&mut database, "GET users SELECT address.city, tags[1] WHERE { userId = 'a' }");
+        assert_eq!(
+            item,
+            HashMap::from([
+                (
+                    String::from("address"),
+                    Value::Map(BTreeMap::from([(String::from("city"), Value::String(String::from("Paris")))]))
+                ),
+                (String::from("tags"), Value::List(vec![Value::String(String::from("beta"))]))
+            ])
+        );
+
+        execute(
+            &mut database,
+            "PUT users { userId: 'c', tags: [1, 2, 3], orders: [{ a: 1, b: 2, c: 3 }, { a: 4 }] }"
+        )
+        .unwrap();
+
+        for projection in ["tags, tags[0]", "tags[0], tags", "tags[1], tags, tags[2]"] {
+            let item = get_item(
+                &mut database,
+                &format!("GET users SELECT {} WHERE {{ userId = 'c' }}", projection)
+            );
+            assert_eq!(
+                item.get("tags"),
+                Some(&Value::List(vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)])),
+                "{}",
+                projection
+            );
+        }
+
+        let item = get_item(
+            &mut database,
+            "GET users SELECT orders[0].b, orders[1].a, orders[0].c, orders[0].b WHERE { userId = 'c' }"
+        );
+        assert_eq!(
+            item.get("orders"),
+            Some(&Value::List(vec![
+                Value::Map(BTreeMap::from([
+                    (String::from("b"), Value::Integer(2)),
+                    (String::from("c"), Value::Integer(3))
+                ])),
+                Value::Map(BTreeMap::from([(String::from("a"), Value::Integer(4))]))
+            ]))
+        );
+
+        execute(&mut database, "DELETE users WHERE { userId = 'c' }").unwrap();
+
+        let (in_paris, _) = scan_page(&mut database, "SCAN users FILTER { address.city = 'Paris' }", "userId");
+        assert_eq!(in_paris, [Value::String(String::from("a"))]);
+
+        let (tagged, _) = scan_page(&mut database, "SCAN users FILTER { attribute_exists(tags[0]) }", "userId");
+        assert_eq!(tagged, [Value::String(String::from("a"))]);
+
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'b' } SET { address.city: address.city + 'by', tags[5]: 'new' }"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "GET users SELECT address, tags WHERE { userId = 'b' }");
+        assert_eq!(
+            item.get("address"),
+            Some(&Value::Map(BTreeMap::from([(
+                String::from("city"),
+                Value::String(String::from("Osloby"))
+            )])))
+        );
+        assert_eq!(item.get("tags"), Some(&Value::List(vec![Value::String(String::from("new"))])));
+
+        assert!(matches!(
+            execute(&mut database, "UPDATE users WHERE { userId = 'b' } SET { profile.bio: 'hi' }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidDocumentPath(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "UPDATE users WHERE { userId = 'b' } SET { userId.x: 1 }"),
+            Err(PliteDbError::EngineError(EngineError::KeyAttributeUpdate(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "GET users WHERE { userId.x = 'b' }"),
+            Err(PliteDbError::EngineError(EngineError::UnsupportedCondition(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'c', address: { city: 'a', city: 'b' } }"),
+            Err(PliteDbError::EvaluationError(EvaluationError::DuplicateMapKey(_)))
+        ));
+    }
+}
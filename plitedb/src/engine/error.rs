@@ -0,0 +1,65 @@
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum EngineError {
+    #[error("Store '{0}' does not exist")]
+    StoreNotFound(String),
+    #[error("Store '{0}' already exists")]
+    StoreAlreadyExists(String),
+    #[error("Store '{0}' cannot use '{1}' as both its hash key and sort key")]
+    ConflictingKeyNames(String, String),
+    #[error("Store '{0}' has no sort key to query")]
+    MissingSortKey(String),
+    #[error("Store '{0}' has no sort key, so it has no partitions to delete")]
+    NotPartitioned(String),
+    #[error("Missing value for key attribute '{0}'")]
+    MissingKeyAttribute(String),
+    #[error("Invalid value for key attribute '{0}'. Expected {1}, found {2}")]
+    InvalidKeyValue(String, &'static str, &'static str),
+    #[error("Item does not exist in store '{0}'")]
+    ItemNotFound(String),
+    #[error("Conditional check failed for item in store '{0}'")]
+    ConditionalCheckFailed(String),
+    #[error("Key attribute '{0}' cannot be updated")]
+    KeyAttributeUpdate(String),
+    #[error("Document path '{0}' does not lead into an existing map or list")]
+    InvalidDocumentPath(String),
+    #[error("{0} cannot apply a {2} to attribute '{1}'")]
+    InvalidUpdateOperand(&'static str, String, &'static str),
+    #[error("{0} cannot combine attribute '{1}' holding a {2} with a {3}")]
+    MismatchedUpdateOperands(&'static str, String, &'static str, &'static str),
+    #[error("Schema of store '{0}' declares attribute '{1}' more than once")]
+    DuplicateSchemaAttribute(String, String),
+    #[error("Schema of store '{0}' cannot redeclare key attribute '{1}'")]
+    SchemaKeyAttribute(String, String),
+    #[error("Item violates the schema of store '{0}': {1}")]
+    SchemaViolation(String, SchemaViolation),
+    #[error("Attribute '{0}' is assigned more than once")]
+    DuplicateAttribute(String),
+    #[error("Attribute '{0}' is constrained more than once")]
+    DuplicateCondition(String),
+    #[error("BEGINS_WITH expects a string prefix, found {0}")]
+    InvalidPrefix(&'static str),
+    #[error("Unsupported condition on attribute '{0}'. Expected an equality condition on a key attribute")]
+    UnsupportedCondition(String),
+    #[error("A transaction is already open")]
+    TransactionAlreadyOpen,
+    #[error("No transaction is open")]
+    NoOpenTransaction,
+    #[error("A script run as one transaction cannot begin, commit or roll back transactions itself")]
+    TransactionControlInScript,
+    #[error("Unsupported key condition. Expected conditions on key attributes joined by AND")]
+    UnsupportedPredicate
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SchemaViolation {
+    #[error("missing required attribute '{0}'")]
+    MissingAttribute(String),
+    #[error("attribute '{0}' cannot be null")]
+    NullAttribute(String),
+    #[error("attribute '{0}' must be a {1}, found {2}")]
+    InvalidType(String, &'static str, &'static str),
+    #[error("attribute '{0}' is not declared")]
+    UndeclaredAttribute(String)
+}
+
+pub type EngineResult<T> = Result<T, EngineError>;
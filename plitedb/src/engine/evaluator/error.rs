@@ -0,0 +1,44 @@
+use crate::query::{
+    cursor::Location,
+    parser::ast::{BinaryOperator, UnaryOperator}
+};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum EvaluationError {
+    #[error("Unknown attribute '{0}'")]
+    UnknownAttribute(String),
+    #[error("Cannot resolve identifier '{0}' without an item")]
+    MissingItemContext(String),
+    #[error("Invalid operand types for '{0:?}': {1} and {2}")]
+    InvalidOperands(BinaryOperator, &'static str, &'static str),
+    #[error("Invalid operand type for '{0:?}': {1}")]
+    InvalidOperand(UnaryOperator, &'static str),
+    #[error("Integer overflow while evaluating '{0}'")]
+    IntegerOverflow(String),
+    #[error("Result of '{0}' is out of range")]
+    OutOfRange(String),
+    #[error("Unknown date unit '{0}' at {1}")]
+    UnknownDateUnit(String, Location),
+    #[error("Non-finite result while evaluating '{0:?}'")]
+    NonFiniteResult(BinaryOperator),
+    #[error("Key '{0}' appears more than once in a map")]
+    DuplicateMapKey(String),
+    #[error("Sets must contain at least one element")]
+    EmptySet,
+    #[error("Invalid element type for {0}: {1}")]
+    InvalidSetElement(&'static str, &'static str),
+    #[error("Unknown function '{0}' at {1}")]
+    UnknownFunction(String, Location),
+    #[error("Function '{0}' at {3} expects {1} arguments, found {2}")]
+    InvalidArgumentCount(String, String, usize, Location),
+    #[error("Invalid argument types for function '{0}' at {2}: ({1})")]
+    InvalidArguments(String, String, Location),
+    #[error("Cannot cast {0} to {1} at {2}")]
+    InvalidCast(&'static str, String, Location),
+    #[error("Condition must evaluate to a boolean, found {0}")]
+    NonBooleanCondition(&'static str),
+    #[error("Division by zero")]
+    DivisionByZero
+}
+
+pub type EvaluationResult<T> = Result<T, EvaluationError>;
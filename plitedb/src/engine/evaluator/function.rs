@@ -0,0 +1,294 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use bigdecimal::{BigDecimal, Signed, ToPrimitive};
+use chrono::{Datelike, Days, NaiveDate, Timelike, Utc};
+
+use crate::{engine::model::Value, query::parser::ast::FunctionCall};
+
+use super::{
+    contains,
+    error::{EvaluationError, EvaluationResult},
+    evaluate
+};
+
+type Function = fn(&FunctionCall, Vec<Value>) -> EvaluationResult<Value>;
+
+// Function names match regardless of case, like keywords. Arguments are checked against the function's
+// arity before it runs. Apart from `coalesce`, every function yields null when any of its arguments is
+// null, like arithmetic does.
+pub fn call(
+    call: &FunctionCall,
+    item: Option<&HashMap<String, Value>>
+) -> EvaluationResult<Value> {
+    let (function, arity): (Function, RangeInclusive<usize>) = match call.name.to_ascii_lowercase().as_str() {
+        "coalesce" => return coalesce(call, item),
+        "size" => (size, 1..=1),
+        "length" => (length, 1..=1),
+        "lower" => (lower, 1..=1),
+        "upper" => (upper, 1..=1),
+        "substr" => (substr, 2..=3),
+        "begins_with" => (begins_with, 2..=2),
+        "contains" => (contains_function, 2..=2),
+        "abs" => (abs, 1..=1),
+        "cast" => (cast, 2..=2),
+        "now" => (now, 0..=0),
+        "date_trunc" => (date_trunc, 2..=2),
+        _ => return Err(EvaluationError::UnknownFunction(call.name.clone(), call.location))
+    };
+
+    if !arity.contains(&call.arguments.len()) {
+        let expected = match arity.start() == arity.end() {
+            true => arity.start().to_string(),
+            false => format!("{} to {}", arity.start(), arity.end())
+        };
+
+        return Err(EvaluationError::InvalidArgumentCount(
+            call.name.clone(),
+            expected,
+            call.arguments.len(),
+            call.location
+        ));
+    }
+
+    let arguments: Vec<Value> = call
+        .arguments
+        .iter()
+        .map(|argument| evaluate(argument, item))
+        .collect::<EvaluationResult<_>>()?;
+
+    if arguments.iter().any(|argument| matches!(argument, Value::Null)) {
+        return Ok(Value::Null);
+    }
+
+    return function(call, arguments);
+}
+
+// Returns the first argument that is neither null nor a missing attribute. Arguments after it are not
+// evaluated.
+fn coalesce(
+    call: &FunctionCall,
+    item: Option<&HashMap<String, Value>>
+) -> EvaluationResult<Value> {
+    if call.arguments.is_empty() {
+        return Err(EvaluationError::InvalidArgumentCount(
+            call.name.clone(),
+            "at least 1".to_string(),
+            0,
+            call.location
+        ));
+    }
+
+    for argument in &call.arguments {
+        match evaluate(argument, item) {
+            Ok(Value::Null) | Err(EvaluationError::UnknownAttribute(_)) => (),
+            result => return result
+        }
+    }
+
+    return Ok(Value::Null);
+}
+
+// Strings are measured in characters, bytes in octets, and lists, maps and sets by their number of
+// entries.
+fn size(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    let size = match arguments.as_slice() {
+        [Value::String(value)] => value.chars().count(),
+        [Value::Bytes(value)] => value.len(),
+        [Value::List(value)] => value.len(),
+        [Value::Map(value)] => value.len(),
+        [Value::StringSet(value)] => value.len(),
+        [Value::NumberSet(value)] => value.len(),
+        [Value::BinarySet(value)] => value.len(),
+        arguments => return Err(invalid_arguments(call, arguments))
+    };
+
+    return Ok(Value::Integer(size as i64));
+}
+
+fn length(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    let length = match arguments.as_slice() {
+        [Value::String(value)] => value.chars().count(),
+        [Value::Bytes(value)] => value.len(),
+        arguments => return Err(invalid_arguments(call, arguments))
+    };
+
+    return Ok(Value::Integer(length as i64));
+}
+
+fn lower(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    return match arguments.as_slice() {
+        [Value::String(value)] => Ok(Value::String(value.to_lowercase())),
+        arguments => Err(invalid_arguments(call, arguments))
+    };
+}
+
+fn upper(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    return match arguments.as_slice() {
+        [Value::String(value)] => Ok(Value::String(value.to_uppercase())),
+        arguments => Err(invalid_arguments(call, arguments))
+    };
+}
+
+// Like SQL, positions count characters from 1. Characters before the first position are skipped, so
+// `substr('abc', 0, 2)` is 'a', and a negative length yields an empty string.
+fn substr(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    let (value, start, length) = match arguments.as_slice() {
+        [Value::String(value), Value::Integer(start)] => (value, *start, None),
+        [Value::String(value), Value::Integer(start), Value::Integer(length)] => (value, *start, Some(*length)),
+        arguments => return Err(invalid_arguments(call, arguments))
+    };
+
+    let end = length.map(|length| start.saturating_add(length.max(0)));
+    let skipped = start.saturating_sub(1).max(0) as usize;
+    let taken = end.map_or(usize::MAX, |end| end.saturating_sub(start.max(1)).max(0) as usize);
+
+    return Ok(Value::String(value.chars().skip(skipped).take(taken).collect()));
+}
+
+fn begins_with(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    return match arguments.as_slice() {
+        [Value::String(value), Value::String(prefix)] => Ok(Value::Boolean(value.starts_with(prefix.as_str()))),
+        [Value::Bytes(value), Value::Bytes(prefix)] => Ok(Value::Boolean(value.starts_with(prefix))),
+        arguments => Err(invalid_arguments(call, arguments))
+    };
+}
+
+fn contains_function(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    return match arguments.as_slice() {
+        [value, operand] => Ok(Value::Boolean(contains(value, operand))),
+        arguments => Err(invalid_arguments(call, arguments))
+    };
+}
+
+fn abs(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    return match arguments.as_slice() {
+        [Value::Integer(value)] => value
+            .checked_abs()
+            .map(Value::Integer)
+            .ok_or_else(|| EvaluationError::IntegerOverflow(call.name.clone())),
+        [Value::Float(value)] => Ok(Value::Float(value.abs())),
+        [Value::Decimal(value)] => Ok(Value::Decimal(value.abs())),
+        arguments => Err(invalid_arguments(call, arguments))
+    };
+}
+
+// The target type is named by a string, as in `cast(age, 'string')`. Casting to an integer truncates
+// toward zero, and casting a float to a decimal is exact.
+fn cast(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    let (value, target) = match <[Value; 2]>::try_from(arguments) {
+        Ok([value, Value::String(target)]) => (value, target),
+        Ok(arguments) => return Err(invalid_arguments(call, &arguments)),
+        Err(arguments) => return Err(invalid_arguments(call, &arguments))
+    };
+
+    let source = value.type_name();
+
+    let cast = match (target.as_str(), value) {
+        ("integer", Value::Integer(value)) => Some(Value::Integer(value)),
+        ("integer", Value::Float(value)) => float_to_integer(value).map(Value::Integer),
+        ("integer", Value::Decimal(value)) => value.with_scale(0).to_i64().map(Value::Integer),
+        ("integer", Value::String(value)) => value.parse().ok().map(Value::Integer),
+        ("float", Value::Integer(value)) => Some(Value::Float(value as f64)),
+        ("float", Value::Float(value)) => Some(Value::Float(value)),
+        ("float", Value::Decimal(value)) => value.to_f64().filter(|value| value.is_finite()).map(Value::Float),
+        ("float", Value::String(value)) => value.parse::<f64>().ok().filter(|value| value.is_finite()).map(Value::Float),
+        ("decimal", Value::Integer(value)) => Some(Value::Decimal(BigDecimal::from(value))),
+        ("decimal", Value::Float(value)) => BigDecimal::try_from(value).ok().map(Value::Decimal),
+        ("decimal", Value::Decimal(value)) => Some(Value::Decimal(value)),
+        ("decimal", Value::String(value)) => value.parse().ok().map(Value::Decimal),
+        ("string", Value::Integer(value)) => Some(Value::String(value.to_string())),
+        ("string", Value::Float(value)) => Some(Value::String(value.to_string())),
+        ("string", Value::Decimal(value)) => Some(Value::String(value.to_string())),
+        ("string", Value::String(value)) => Some(Value::String(value)),
+        ("string", Value::Boolean(value)) => Some(Value::String(value.to_string())),
+        ("string", Value::Timestamp(value)) => Some(Value::String(value.to_rfc3339())),
+        ("boolean", Value::Boolean(value)) => Some(Value::Boolean(value)),
+        ("boolean", Value::String(value)) => value.parse().ok().map(Value::Boolean),
+        _ => None
+    };
+
+    return cast.ok_or(EvaluationError::InvalidCast(source, target, call.location));
+}
+
+fn float_to_integer(value: f64) -> Option<i64> {
+    let value = value.trunc();
+
+    return (value >= i64::MIN as f64 && value < i64::MAX as f64).then_some(value as i64);
+}
+
+fn now(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    return match arguments.as_slice() {
+        [] => Ok(Value::Timestamp(Utc::now())),
+        arguments => Err(invalid_arguments(call, arguments))
+    };
+}
+
+// Weeks start on Monday, as in ISO 8601.
+fn date_trunc(
+    call: &FunctionCall,
+    arguments: Vec<Value>
+) -> EvaluationResult<Value> {
+    let (unit, timestamp) = match arguments.as_slice() {
+        [Value::String(unit), Value::Timestamp(timestamp)] => (unit, timestamp),
+        arguments => return Err(invalid_arguments(call, arguments))
+    };
+
+    let date = timestamp.date_naive();
+    let time = timestamp.time();
+
+    let truncated = match unit.as_str() {
+        "second" => date.and_hms_opt(time.hour(), time.minute(), time.second()),
+        "minute" => date.and_hms_opt(time.hour(), time.minute(), 0),
+        "hour" => date.and_hms_opt(time.hour(), 0, 0),
+        "day" => date.and_hms_opt(0, 0, 0),
+        "week" => date
+            .checked_sub_days(Days::new(date.weekday().num_days_from_monday() as u64))
+            .and_then(|date| date.and_hms_opt(0, 0, 0)),
+        "month" => NaiveDate::from_ymd_opt(date.year(), date.month(), 1).and_then(|date| date.and_hms_opt(0, 0, 0)),
+        "year" => NaiveDate::from_ymd_opt(date.year(), 1, 1).and_then(|date| date.and_hms_opt(0, 0, 0)),
+        _ => return Err(EvaluationError::UnknownDateUnit(unit.clone(), call.location))
+    };
+
+    return truncated
+        .map(|truncated| Value::Timestamp(truncated.and_utc()))
+        .ok_or_else(|| EvaluationError::OutOfRange(call.name.clone()));
+}
+
+fn invalid_arguments(
+    call: &FunctionCall,
+    arguments: &[Value]
+) -> EvaluationError {
+    let types = arguments.iter().map(Value::type_name).collect::<Vec<_>>().join(", ");
+
+    return EvaluationError::InvalidArguments(call.name.clone(), types, call.location);
+}
@@ -0,0 +1,589 @@
+pub mod error;
+mod function;
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap}
+};
+
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+
+use crate::query::parser::ast::{AttributePath, BinaryOperation, BinaryOperator, ComparisonOperator, Expression, Predicate, UnaryOperation, UnaryOperator};
+
+use super::{document, model::Value};
+
+use error::{EvaluationError, EvaluationResult};
+
+pub fn evaluate(
+    expression: &Expression,
+    item: Option<&HashMap<String, Value>>
+) -> EvaluationResult<Value> {
+    return match expression {
+        Expression::Literal(value) => Ok(Value::from(value)),
+        Expression::Path(path) => resolve_path(path, item),
+        Expression::List(elements) => elements
+            .iter()
+            .map(|element| evaluate(element, item))
+            .collect::<EvaluationResult<_>>()
+            .map(Value::List),
+        Expression::Map(entries) => {
+            let mut map = BTreeMap::new();
+
+            for (name, value) in entries {
+                if map.insert(name.clone(), evaluate(value, item)?).is_some() {
+                    return Err(EvaluationError::DuplicateMapKey(name.clone()));
+                }
+            }
+
+            Ok(Value::Map(map))
+        },
+        Expression::Set(elements) => elements
+            .iter()
+            .map(|element| evaluate(element, item))
+            .collect::<EvaluationResult<_>>()
+            .and_then(evaluate_set),
+        Expression::FunctionCall(call) => function::call(call, item),
+        Expression::BinaryOperation(operation) => evaluate_binary_operation(operation, item),
+        Expression::UnaryOperation(operation) => evaluate_unary_operation(operation, item)
+    };
+}
+
+pub fn evaluate_predicate(
+    predicate: &Predicate,
+    item: &HashMap<String, Value>
+) -> EvaluationResult<bool> {
+    return match predicate {
+        Predicate::Comparison { left, operator, right } => {
+            let (Some(left), Some(right)) = (evaluate_operand(left, item)?, evaluate_operand(right, item)?)
+            else {
+                return Ok(false);
+            };
+
+            Ok(compare(&left, *operator, &right))
+        },
+        Predicate::Between { value, low, high } => {
+            let (Some(value), Some(low), Some(high)) = (
+                evaluate_operand(value, item)?,
+                evaluate_operand(low, item)?,
+                evaluate_operand(high, item)?
+            )
+            else {
+                return Ok(false);
+            };
+
+            Ok(compare(&value, ComparisonOperator::GreaterThanOrEqual, &low) && compare(&value, ComparisonOperator::LessThanOrEqual, &high))
+        },
+        Predicate::BeginsWith { value, prefix } => match (evaluate_operand(value, item)?, evaluate_operand(prefix, item)?) {
+            (Some(Value::String(value)), Some(Value::String(prefix))) => Ok(value.starts_with(&prefix)),
+            (Some(Value::Bytes(value)), Some(Value::Bytes(prefix))) => Ok(value.starts_with(&prefix)),
+            _ => Ok(false)
+        },
+        Predicate::AttributeExists { path } => Ok(document::get(item, path).is_some()),
+        Predicate::AttributeNotExists { path } => Ok(document::get(item, path).is_none()),
+        Predicate::Expression(expression) => match evaluate_operand(expression, item)? {
+            Some(Value::Boolean(value)) => Ok(value),
+            Some(Value::Null) | None => Ok(false),
+            Some(value) => Err(EvaluationError::NonBooleanCondition(value.type_name()))
+        },
+        Predicate::Not(predicate) => Ok(!evaluate_predicate(predicate, item)?),
+        Predicate::And(left, right) => Ok(evaluate_predicate(left, item)? && evaluate_predicate(right, item)?),
+        Predicate::Or(left, right) => Ok(evaluate_predicate(left, item)? || evaluate_predicate(right, item)?)
+    };
+}
+
+// An operand that refers to an attribute the item does not have resolves to nothing, which makes the
+// condition using it false.
+fn evaluate_operand(
+    expression: &Expression,
+    item: &HashMap<String, Value>
+) -> EvaluationResult<Option<Value>> {
+    return match evaluate(expression, Some(item)) {
+        Ok(value) => Ok(Some(value)),
+        Err(EvaluationError::UnknownAttribute(_)) => Ok(None),
+        Err(error) => Err(error)
+    };
+}
+
+// Strings and bytes contain their substrings, sets their members and lists any element equal to the
+// operand.
+fn contains(
+    attribute: &Value,
+    operand: &Value
+) -> bool {
+    return match (attribute, operand) {
+        (Value::String(attribute), Value::String(operand)) => attribute.contains(operand.as_str()),
+        (Value::Bytes(attribute), Value::Bytes(operand)) => operand.is_empty() || attribute.windows(operand.len()).any(|window| window == operand.as_slice()),
+        (Value::StringSet(set), Value::String(operand)) => set.contains(operand),
+        (Value::NumberSet(set), operand @ (Value::Integer(_) | Value::Float(_) | Value::Decimal(_))) => set.contains(operand),
+        (Value::BinarySet(set), Value::Bytes(operand)) => set.contains(operand),
+        (Value::List(list), operand) => list.iter().any(|element| compare(element, ComparisonOperator::Equal, operand)),
+        _ => false
+    };
+}
+
+// Lists, maps and sets can only be tested for equality. Values of types that cannot be compared are
+// unequal and unordered.
+pub fn compare(
+    left: &Value,
+    operator: ComparisonOperator,
+    right: &Value
+) -> bool {
+    if let (Value::List(_), Value::List(_))
+    | (Value::Map(_), Value::Map(_))
+    | (Value::StringSet(_), Value::StringSet(_))
+    | (Value::NumberSet(_), Value::NumberSet(_))
+    | (Value::BinarySet(_), Value::BinarySet(_)) = (left, right)
+    {
+        return match operator {
+            ComparisonOperator::Equal => left == right,
+            ComparisonOperator::NotEqual => left != right,
+            _ => false
+        };
+    }
+
+    let ordering = match (left, right) {
+        // Numbers are ordered exactly, as keys are, except that NaN is unordered.
+        (Value::Float(float), _) | (_, Value::Float(float)) if float.is_nan() => None,
+        (Value::Integer(_) | Value::Float(_) | Value::Decimal(_), Value::Integer(_) | Value::Float(_) | Value::Decimal(_)) => Some(left.cmp(right)),
+        (Value::String(left), Value::String(right)) => left.partial_cmp(right),
+        (Value::Boolean(left), Value::Boolean(right)) => left.partial_cmp(right),
+        (Value::Bytes(left), Value::Bytes(right)) => left.partial_cmp(right),
+        (Value::Timestamp(left), Value::Timestamp(right)) => left.partial_cmp(right),
+        (Value::Duration(left), Value::Duration(right)) => left.partial_cmp(right),
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        _ => None
+    };
+
+    return match ordering {
+        Some(ordering) => match operator {
+            ComparisonOperator::Equal => ordering.is_eq(),
+            ComparisonOperator::NotEqual => ordering.is_ne(),
+            ComparisonOperator::GreaterThan => ordering.is_gt(),
+            ComparisonOperator::GreaterThanOrEqual => ordering.is_ge(),
+            ComparisonOperator::LessThan => ordering.is_lt(),
+            ComparisonOperator::LessThanOrEqual => ordering.is_le()
+        },
+        None => operator == ComparisonOperator::NotEqual
+    };
+}
+
+// Sets are never empty and all of their elements are strings, numbers or bytes. Duplicate elements
+// collapse into one.
+fn evaluate_set(elements: Vec<Value>) -> EvaluationResult<Value> {
+    return match elements.first() {
+        None => Err(EvaluationError::EmptySet),
+        Some(Value::String(_)) => elements
+            .into_iter()
+            .map(|element| match element {
+                Value::String(element) => Ok(element),
+                element => Err(EvaluationError::InvalidSetElement("string set", element.type_name()))
+            })
+            .collect::<EvaluationResult<_>>()
+            .map(Value::StringSet),
+        Some(Value::Integer(_) | Value::Float(_) | Value::Decimal(_)) => elements
+            .into_iter()
+            .map(|element| match element {
+                Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => Ok(element),
+                element => Err(EvaluationError::InvalidSetElement("number set", element.type_name()))
+            })
+            .collect::<EvaluationResult<_>>()
+            .map(Value::NumberSet),
+        Some(Value::Bytes(_)) => elements
+            .into_iter()
+            .map(|element| match element {
+                Value::Bytes(element) => Ok(element),
+                element => Err(EvaluationError::InvalidSetElement("binary set", element.type_name()))
+            })
+            .collect::<EvaluationResult<_>>()
+            .map(Value::BinarySet),
+        Some(element) => Err(EvaluationError::InvalidSetElement("set", element.type_name()))
+    };
+}
+
+fn resolve_path(
+    path: &AttributePath,
+    item: Option<&HashMap<String, Value>>
+) -> EvaluationResult<Value> {
+    let attributes = item.ok_or_else(|| EvaluationError::MissingItemContext(path.to_string()))?;
+
+    return document::get(attributes, path)
+        .cloned()
+        .ok_or_else(|| EvaluationError::UnknownAttribute(path.to_string()));
+}
+
+fn evaluate_binary_operation(
+    operation: &BinaryOperation,
+    item: Option<&HashMap<String, Value>>
+) -> EvaluationResult<Value> {
+    let left = evaluate(&operation.left, item)?;
+    let right = evaluate(&operation.right, item)?;
+
+    return apply_binary_operator(operation.operator, left, right);
+}
+
+fn evaluate_unary_operation(
+    operation: &UnaryOperation,
+    item: Option<&HashMap<String, Value>>
+) -> EvaluationResult<Value> {
+    let operand = evaluate(&operation.operand, item)?;
+
+    return match (operation.operator, operand) {
+        (UnaryOperator::Negate, Value::Integer(value)) => value
+            .checked_neg()
+            .map(Value::Integer)
+            .ok_or_else(|| EvaluationError::IntegerOverflow(format!("{:?}", operation.operator))),
+        (UnaryOperator::Negate, Value::Float(value)) => Ok(Value::Float(-value)),
+        (UnaryOperator::Negate, Value::Decimal(value)) => Ok(Value::Decimal(-value)),
+        (UnaryOperator::Negate, Value::Duration(value)) => Ok(Value::Duration(-value)),
+        (_, Value::Null) => Ok(Value::Null),
+        (operator, operand) => Err(EvaluationError::InvalidOperand(operator, operand.type_name()))
+    };
+}
+
+pub fn apply_binary_operator(
+    operator: BinaryOperator,
+    left: Value,
+    right: Value
+) -> EvaluationResult<Value> {
+    return match (left, right) {
+        (Value::Null, _) | (_, Value::Null) => Ok(Value::Null),
+        (left @ (Value::Timestamp(_) | Value::Duration(_)), right) | (left, right @ (Value::Timestamp(_) | Value::Duration(_))) => {
+            apply_temporal_operator(operator, left, right)
+        },
+        (Value::Integer(left), Value::Integer(right)) => apply_integer_operator(operator, left, right),
+        (Value::Integer(left), Value::Float(right)) => apply_float_operator(operator, left as f64, right),
+        (Value::Float(left), Value::Integer(right)) => apply_float_operator(operator, left, right as f64),
+        (Value::Float(left), Value::Float(right)) => apply_float_operator(operator, left, right),
+        (Value::Decimal(left), Value::Decimal(right)) => apply_decimal_operator(operator, left, right),
+        (Value::Decimal(left), Value::Integer(right)) => apply_decimal_operator(operator, left, BigDecimal::from(right)),
+        (Value::Integer(left), Value::Decimal(right)) => apply_decimal_operator(operator, BigDecimal::from(left), right),
+        (Value::String(left), Value::String(right)) if operator == BinaryOperator::Add => Ok(Value::String(left + &right)),
+        (left, right) => Err(EvaluationError::InvalidOperands(operator, left.type_name(), right.type_name()))
+    };
+}
+
+// Integer arithmetic fails on overflow, and `/` and `%` truncate toward zero. A negative exponent
+// produces a float.
+fn apply_integer_operator(
+    operator: BinaryOperator,
+    left: i64,
+    right: i64
+) -> EvaluationResult<Value> {
+    let result = match operator {
+        BinaryOperator::Add => left.checked_add(right),
+        BinaryOperator::Subtract => left.checked_sub(right),
+        BinaryOperator::Multiply => left.checked_mul(right),
+        BinaryOperator::Divide | BinaryOperator::Modulus if right == 0 => return Err(EvaluationError::DivisionByZero),
+        BinaryOperator::Divide => left.checked_div(right),
+        BinaryOperator::Modulus => left.checked_rem(right),
+        BinaryOperator::Exponentiate if right < 0 => return apply_float_operator(operator, left as f64, right as f64),
+        BinaryOperator::Exponentiate => u32::try_from(right).ok().and_then(|exponent| left.checked_pow(exponent))
+    };
+
+    return result
+        .map(Value::Integer)
+        .ok_or_else(|| EvaluationError::IntegerOverflow(format!("{:?}", operator)));
+}
+
+fn apply_float_operator(
+    operator: BinaryOperator,
+    left: f64,
+    right: f64
+) -> EvaluationResult<Value> {
+    let result = match operator {
+        BinaryOperator::Add => left + right,
+        BinaryOperator::Subtract => left - right,
+        BinaryOperator::Multiply => left * right,
+        BinaryOperator::Divide | BinaryOperator::Modulus if right == 0.0 => return Err(EvaluationError::DivisionByZero),
+        BinaryOperator::Divide => left / right,
+        BinaryOperator::Modulus => left % right,
+        BinaryOperator::Exponentiate => left.powf(right)
+    };
+
+    if !result.is_finite() {
+        return Err(EvaluationError::NonFiniteResult(operator));
+    }
+
+    return Ok(Value::Float(result));
+}
+
+// Division and negative powers round to 100 significant digits, everything else stays exact.
+fn apply_decimal_operator(
+    operator: BinaryOperator,
+    left: BigDecimal,
+    right: BigDecimal
+) -> EvaluationResult<Value> {
+    let result = match operator {
+        BinaryOperator::Add => left + right,
+        BinaryOperator::Subtract => left - right,
+        BinaryOperator::Multiply => left * right,
+        BinaryOperator::Divide | BinaryOperator::Modulus if right.is_zero() => return Err(EvaluationError::DivisionByZero),
+        BinaryOperator::Divide => left / right,
+        BinaryOperator::Modulus => left % right,
+        BinaryOperator::Exponentiate => match right.is_integer().then(|| right.to_i64()).flatten() {
+            Some(exponent) if exponent < 0 && left.is_zero() => return Err(EvaluationError::DivisionByZero),
+            Some(exponent) => left.powi(exponent),
+            None => return Err(EvaluationError::InvalidOperands(operator, "decimal", "non-integral decimal"))
+        }
+    };
+
+    return Ok(Value::Decimal(result));
+}
+
+// Scaling a duration goes through a 32-bit factor, so larger integers are reported as out of range.
+fn apply_temporal_operator(
+    operator: BinaryOperator,
+    left: Value,
+    right: Value
+) -> EvaluationResult<Value> {
+    let result = match (operator, &left, &right) {
+        (BinaryOperator::Add, Value::Timestamp(timestamp), Value::Duration(duration))
+        | (BinaryOperator::Add, Value::Duration(duration), Value::Timestamp(timestamp)) => timestamp.checked_add_signed(*duration).map(Value::Timestamp),
+        (BinaryOperator::Subtract, Value::Timestamp(timestamp), Value::Duration(duration)) => timestamp.checked_sub_signed(*duration).map(Value::Timestamp),
+        (BinaryOperator::Subtract, Value::Timestamp(left), Value::Timestamp(right)) => Some(Value::Duration(left.signed_duration_since(*right))),
+        (BinaryOperator::Add, Value::Duration(left), Value::Duration(right)) => left.checked_add(right).map(Value::Duration),
+        (BinaryOperator::Subtract, Value::Duration(left), Value::Duration(right)) => left.checked_sub(right).map(Value::Duration),
+        (BinaryOperator::Multiply, Value::Duration(duration), Value::Integer(factor))
+        | (BinaryOperator::Multiply, Value::Integer(factor), Value::Duration(duration)) => {
+            i32::try_from(*factor).ok().and_then(|factor| duration.checked_mul(factor)).map(Value::Duration)
+        },
+        (BinaryOperator::Divide, Value::Duration(_), Value::Integer(0)) => return Err(EvaluationError::DivisionByZero),
+        (BinaryOperator::Divide, Value::Duration(duration), Value::Integer(divisor)) => i32::try_from(*divisor)
+            .ok()
+            .and_then(|divisor| duration.checked_div(divisor))
+            .map(Value::Duration),
+        _ => return Err(EvaluationError::InvalidOperands(operator, left.type_name(), right.type_name()))
+    };
+
+    return result.ok_or_else(|| EvaluationError::OutOfRange(format!("{:?}", operator)));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::query::{
+        cursor::{Location, PeekingCursor},
+        lexer::tokenize,
+        parser::{error::ParserError, expression::parse_expression}
+    };
+
+    use super::*;
+
+    fn evaluate_input(
+        input: &str,
+        item: Option<&HashMap<String, Value>>
+    ) -> EvaluationResult<Value> {
+        let mut tokens = PeekingCursor::new(tokenize(input).unwrap().into_iter());
+        let expression = parse_expression(&mut tokens, 0).unwrap();
+
+        return evaluate(&expression, item);
+    }
+
+    #[test]
+    fn evaluates_integer_arithmetic() {
+        assert_eq!(evaluate_input("10 - 2 - 3", None).unwrap(), Value::Integer(5));
+        assert_eq!(evaluate_input("2 + 3 * 4 ^ 2", None).unwrap(), Value::Integer(50));
+        assert_eq!(evaluate_input("2 ^ 3 ^ 2", None).unwrap(), Value::Integer(512));
+        assert_eq!(evaluate_input("-7 / 2", None).unwrap(), Value::Integer(-3));
+        assert_eq!(evaluate_input("-7 % 2", None).unwrap(), Value::Integer(-1));
+    }
+
+    #[test]
+    fn promotes_mixed_arithmetic_to_float() {
+        assert_eq!(evaluate_input("1 + 0.5", None).unwrap(), Value::Float(1.5));
+        assert_eq!(evaluate_input("7.0 / 2", None).unwrap(), Value::Float(3.5));
+        assert_eq!(evaluate_input("2 ^ -1", None).unwrap(), Value::Float(0.5));
+    }
+
+    #[test]
+    fn rejects_division_by_zero_and_overflow() {
+        assert!(matches!(evaluate_input("1 / 0", None), Err(EvaluationError::DivisionByZero)));
+        assert!(matches!(evaluate_input("1.5 % 0", None), Err(EvaluationError::DivisionByZero)));
+        assert!(matches!(
+            evaluate_input("9223372036854775807 + 1", None),
+            Err(EvaluationError::IntegerOverflow(_))
+        ));
+        assert!(matches!(
+            evaluate_input("10 ^ 100", None),
+            Err(EvaluationError::IntegerOverflow(_))
+        ));
+        assert!(matches!(
+            evaluate_input("10.0 ^ 1000", None),
+            Err(EvaluationError::NonFiniteResult(_))
+        ));
+
+        // The smallest integer can be written, but its magnitude alone is out of range.
+        assert_eq!(evaluate_input("-9223372036854775808", None).unwrap(), Value::Integer(i64::MIN));
+        assert!(matches!(
+            evaluate_input("-9223372036854775808 - 1", None),
+            Err(EvaluationError::IntegerOverflow(_))
+        ));
+        let mut tokens = PeekingCursor::new(tokenize("1 + 9223372036854775808").unwrap().into_iter());
+        assert!(matches!(
+            parse_expression(&mut tokens, 0),
+            Err(ParserError::IntegerOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn resolves_identifiers_from_item() {
+        let item = HashMap::from([
+            (String::from("age"), Value::Integer(30)),
+            (String::from("name"), Value::String(String::from("Alice")))
+        ]);
+
+        assert_eq!(evaluate_input("age + 1", Some(&item)).unwrap(), Value::Integer(31));
+        assert_eq!(
+            evaluate_input("name + ' Smith'", Some(&item)).unwrap(),
+            Value::String(String::from("Alice Smith"))
+        );
+        assert!(matches!(
+            evaluate_input("height", Some(&item)),
+            Err(EvaluationError::UnknownAttribute(_))
+        ));
+        assert!(matches!(
+            evaluate_input("age", None),
+            Err(EvaluationError::MissingItemContext(_))
+        ));
+        assert!(matches!(
+            evaluate_input("name * 2", Some(&item)),
+            Err(EvaluationError::InvalidOperands(..))
+        ));
+    }
+
+    #[test]
+    fn keeps_decimal_arithmetic_exact() {
+        let decimal = |value: &str| Value::Decimal(value.parse().unwrap());
+
+        assert_eq!(evaluate_input("0.1d + 0.2d", None).unwrap(), decimal("0.3"));
+        assert_eq!(evaluate_input("19.99d * 3 - 0.97d", None).unwrap(), decimal("59"));
+        assert_eq!(evaluate_input("-7.5d % 2", None).unwrap(), decimal("-1.5"));
+        assert_eq!(evaluate_input("1.1d ^ 2", None).unwrap(), decimal("1.21"));
+        assert_eq!(
+            evaluate_input("1d / 3", None).unwrap(),
+            decimal(&format!("0.{}", "3".repeat(100)))
+        );
+
+        assert!(compare(
+            &decimal("0.3"),
+            ComparisonOperator::Equal,
+            &evaluate_input("0.1d + 0.2d", None).unwrap()
+        ));
+        assert!(compare(&decimal("0.5"), ComparisonOperator::Equal, &Value::Float(0.5)));
+        assert!(compare(&decimal("0.1"), ComparisonOperator::LessThan, &Value::Float(0.1)));
+
+        assert!(matches!(
+            evaluate_input("1.5d + 1.5", None),
+            Err(EvaluationError::InvalidOperands(..))
+        ));
+        assert!(matches!(evaluate_input("1d / 0", None), Err(EvaluationError::DivisionByZero)));
+        assert!(matches!(
+            evaluate_input("2d ^ 0.5d", None),
+            Err(EvaluationError::InvalidOperands(..))
+        ));
+    }
+
+    #[test]
+    fn compares_numbers_exactly() {
+        // 2^53 + 1 has no float representation, so converting it to a float would make these equal.
+        let integer = Value::Integer(9007199254740993);
+        let float = Value::Float(9007199254740992.0);
+
+        assert!(!compare(&integer, ComparisonOperator::Equal, &float));
+        assert!(compare(&integer, ComparisonOperator::GreaterThan, &float));
+        assert!(compare(&float, ComparisonOperator::LessThan, &integer));
+        assert_eq!(integer.cmp(&float), Ordering::Greater);
+        assert!(compare(&Value::Integer(1), ComparisonOperator::Equal, &Value::Float(1.0)));
+
+        let nan = Value::Float(f64::NAN);
+        assert!(!compare(&nan, ComparisonOperator::Equal, &nan));
+        assert!(compare(&nan, ComparisonOperator::NotEqual, &nan));
+        assert!(!compare(&Value::Integer(0), ComparisonOperator::LessThan, &nan));
+    }
+
+    #[test]
+    fn calls_builtin_functions() {
+        let item = HashMap::from([
+            (String::from("name"), Value::String(String::from("Ada Lovelace"))),
+            (String::from("nickname"), Value::Null),
+            (String::from("balance"), Value::Integer(-42))
+        ]);
+        let string = |value: &str| Value::String(value.to_string());
+
+        assert_eq!(evaluate_input("upper(substr(name, 1, 3))", Some(&item)).unwrap(), string("ADA"));
+        assert_eq!(
+            evaluate_input("lower(substr(name, 5))", Some(&item)).unwrap(),
+            string("lovelace")
+        );
+        assert_eq!(evaluate_input("substr('abc', 0, 2)", None).unwrap(), string("a"));
+        assert_eq!(
+            evaluate_input("length(name) + size([1, 2])", Some(&item)).unwrap(),
+            Value::Integer(14)
+        );
+        assert_eq!(
+            evaluate_input("begins_with(name, 'Ada')", Some(&item)).unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            evaluate_input("contains(<<'a', 'b'>>, 'c')", None).unwrap(),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            evaluate_input("Begins_With(LOWER(name), 'ada')", Some(&item)).unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            evaluate_input("abs(balance) + abs(-1.5d)", Some(&item)).unwrap(),
+            Value::Decimal("43.5".parse().unwrap())
+        );
+        assert_eq!(
+            evaluate_input("coalesce(nickname, missing, name)", Some(&item)).unwrap(),
+            string("Ada Lovelace")
+        );
+        assert_eq!(
+            evaluate_input("cast('12', 'integer') + cast(2.9, 'integer')", None).unwrap(),
+            Value::Integer(14)
+        );
+        assert_eq!(
+            evaluate_input("cast(0.5, 'decimal')", None).unwrap(),
+            Value::Decimal("0.5".parse().unwrap())
+        );
+        assert_eq!(evaluate_input("cast(balance, 'string')", Some(&item)).unwrap(), string("-42"));
+        assert_eq!(evaluate_input("lower(nickname)", Some(&item)).unwrap(), Value::Null);
+
+        let location = |line, column| Location { line, column };
+
+        assert!(matches!(
+            evaluate_input("1 +\n  lower(1)", None),
+            Err(EvaluationError::InvalidArguments(_, types, at)) if types == "integer" && at == location(2, 3)
+        ));
+        assert!(matches!(
+            evaluate_input("substr('a')", None),
+            Err(EvaluationError::InvalidArgumentCount(_, expected, 1, at)) if expected == "2 to 3" && at == location(1, 1)
+        ));
+        assert!(matches!(
+            evaluate_input("  reverse('a')", None),
+            Err(EvaluationError::UnknownFunction(_, at)) if at == location(1, 3)
+        ));
+        assert!(matches!(
+            evaluate_input("cast('abc', 'integer')", None),
+            Err(EvaluationError::InvalidCast("string", ..))
+        ));
+        assert!(matches!(
+            evaluate_input("coalesce()", None),
+            Err(EvaluationError::InvalidArgumentCount(..))
+        ));
+    }
+
+    #[test]
+    fn propagates_null_through_arithmetic() {
+        let item = HashMap::from([(String::from("score"), Value::Null)]);
+
+        assert_eq!(evaluate_input("score + 1", Some(&item)).unwrap(), Value::Null);
+        assert_eq!(evaluate_input("-null", None).unwrap(), Value::Null);
+        assert_eq!(evaluate_input("'a' + null", None).unwrap(), Value::Null);
+
+        assert!(compare(&Value::Null, ComparisonOperator::Equal, &Value::Null));
+        assert!(compare(&Value::Null, ComparisonOperator::NotEqual, &Value::Integer(0)));
+        assert!(!compare(&Value::Null, ComparisonOperator::LessThan, &Value::Integer(0)));
+    }
+}
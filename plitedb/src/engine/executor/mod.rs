@@ -0,0 +1,741 @@
+mod read;
+mod write;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    error::{PliteDbError, PliteDbResult},
+    query::parser::ast::{Assignment, ComparisonOperator, Expression, KeyType, Predicate, Schema, Statement, WriteCondition}
+};
+
+use super::{
+    document,
+    error::{EngineError, EngineResult},
+    evaluator::{evaluate, evaluate_predicate},
+    model::{Database, PartitionStore, SimpleStore, Store, Value},
+    schema
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryOutput {
+    Item(Option<HashMap<String, Value>>),
+    Items(Vec<HashMap<String, Value>>),
+    Page {
+        items: Vec<HashMap<String, Value>>,
+        last_evaluated_key: Option<HashMap<String, Value>>
+    },
+    Written,
+    Deleted(usize),
+    StoreCreated(String),
+    StoreDropped(String),
+    TransactionStarted,
+    Committed,
+    RolledBack
+}
+
+impl Database {
+    pub fn execute(
+        &mut self,
+        statement: Statement
+    ) -> PliteDbResult<QueryOutput> {
+        return match statement {
+            Statement::PutItem {
+                store_name,
+                assignments,
+                condition
+            } => write::put_item(self.store_mut(&store_name)?, &assignments, condition.as_ref()),
+            Statement::GetItem {
+                store_name,
+                projection,
+                predicate
+            } => read::get_item(self.store(&store_name)?, projection.as_deref(), &predicate),
+            Statement::Query { store_name, predicate } => read::query(self.store(&store_name)?, &predicate),
+            Statement::Scan {
+                store_name,
+                filter,
+                limit,
+                start_after
+            } => read::scan(self.store(&store_name)?, filter.as_ref(), limit, start_after.as_deref()),
+            Statement::UpdateItem {
+                store_name,
+                predicate,
+                assignments,
+                additions,
+                deletions,
+                upsert,
+                condition
+            } => write::update_item(
+                self.store_mut(&store_name)?,
+                &predicate,
+                &assignments,
+                &additions,
+                &deletions,
+                upsert,
+                condition.as_ref()
+            ),
+            Statement::DeleteItem {
+                store_name,
+                predicate,
+                entire_partition,
+                condition
+            } => write::delete_item(self.store_mut(&store_name)?, &predicate, entire_partition, condition.as_ref()),
+            Statement::CreateStore {
+                store_name,
+                hash_key,
+                sort_key,
+                schema
+            } => {
+                let store = match sort_key {
+                    Some(sort_key) => Store::Partition(PartitionStore::new(
+                        store_name.clone(),
+                        hash_key.name,
+                        hash_key.key_type,
+                        sort_key.name,
+                        sort_key.key_type,
+                        schema
+                    )),
+                    None => Store::Simple(SimpleStore::new(store_name.clone(), hash_key.name, hash_key.key_type, schema))
+                };
+
+                self.create_store(store)?;
+
+                Ok(QueryOutput::StoreCreated(store_name))
+            },
+            Statement::DropStore { store_name } => {
+                self.drop_store(&store_name)?;
+
+                Ok(QueryOutput::StoreDropped(store_name))
+            },
+            Statement::Begin => {
+                self.begin()?;
+
+                Ok(QueryOutput::TransactionStarted)
+            },
+            Statement::Commit => {
+                self.commit()?;
+
+                Ok(QueryOutput::Committed)
+            },
+            Statement::Rollback => {
+                self.rollback()?;
+
+                Ok(QueryOutput::RolledBack)
+            }
+        };
+    }
+
+    // Runs the statements in order and stops at the first failure, which reports the index of the failing
+    // statement along with the outputs of those before it. An atomic script runs as a single transaction,
+    // so that a failure leaves the database as it was before the script and there are no outputs to keep.
+    pub fn execute_script(
+        &mut self,
+        statements: Vec<Statement>,
+        atomic: bool
+    ) -> PliteDbResult<Vec<QueryOutput>> {
+        if atomic {
+            if statements
+                .iter()
+                .any(|statement| matches!(statement, Statement::Begin | Statement::Commit | Statement::Rollback))
+            {
+                return Err(EngineError::TransactionControlInScript.into());
+            }
+
+            self.begin()?;
+        }
+
+        let mut outputs = Vec::with_capacity(statements.len());
+
+        for (index, statement) in statements.into_iter().enumerate() {
+            match self.execute(statement) {
+                Ok(output) => outputs.push(output),
+                Err(error) if atomic => {
+                    self.rollback()?;
+                    return Err(PliteDbError::ScriptError(index, Vec::new(), Box::new(error)));
+                },
+                Err(error) => return Err(PliteDbError::ScriptError(index, outputs, Box::new(error)))
+            }
+        }
+
+        if atomic {
+            self.commit()?;
+        }
+
+        return Ok(outputs);
+    }
+}
+
+fn validate_key_value(
+    name: &str,
+    key_type: KeyType,
+    value: Value
+) -> EngineResult<Value> {
+    return match (key_type, &value) {
+        (KeyType::Integer, Value::Integer(_))
+        | (KeyType::String, Value::String(_))
+        | (KeyType::Binary, Value::Bytes(_))
+        | (KeyType::Timestamp, Value::Timestamp(_))
+        | (KeyType::Decimal, Value::Decimal(_)) => Ok(value),
+        _ => Err(EngineError::InvalidKeyValue(
+            name.to_string(),
+            key_type.name(),
+            value.type_name()
+        ))
+    };
+}
+
+fn key_attribute(
+    attributes: &HashMap<String, Value>,
+    name: &str,
+    key_type: KeyType
+) -> EngineResult<Value> {
+    let value = attributes
+        .get(name)
+        .cloned()
+        .ok_or_else(|| EngineError::MissingKeyAttribute(name.to_string()))?;
+
+    return validate_key_value(name, key_type, value);
+}
+
+// Assignments are applied in order, so a nested path can be assigned after its parent.
+fn evaluate_assignments(assignments: &[Assignment]) -> PliteDbResult<HashMap<String, Value>> {
+    let mut attributes = HashMap::with_capacity(assignments.len());
+    let mut assigned = HashSet::with_capacity(assignments.len());
+
+    for assignment in assignments {
+        if !assigned.insert(&assignment.path) {
+            return Err(EngineError::DuplicateAttribute(assignment.path.to_string()).into());
+        }
+
+        let value = evaluate(&assignment.value, None)?;
+
+        document::set(&mut attributes, &assignment.path, value)?;
+    }
+
+    return Ok(attributes);
+}
+
+// Items are looked up by equality conditions on their key attributes, joined by AND.
+fn evaluate_key_conditions(predicate: &Predicate) -> PliteDbResult<HashMap<String, Value>> {
+    let mut key_conditions = HashMap::new();
+
+    for conjunct in predicate.conjuncts() {
+        let Predicate::Comparison {
+            left: Expression::Path(path),
+            operator: ComparisonOperator::Equal,
+            right
+        } = conjunct
+        else {
+            return Err(unsupported_condition(conjunct).into());
+        };
+
+        if !path.is_top_level() {
+            return Err(EngineError::UnsupportedCondition(path.to_string()).into());
+        }
+
+        let value = evaluate(right, None)?;
+
+        if key_conditions.insert(path.name.clone(), value).is_some() {
+            return Err(EngineError::DuplicateCondition(path.name.clone()).into());
+        }
+    }
+
+    return Ok(key_conditions);
+}
+
+fn unsupported_condition(predicate: &Predicate) -> EngineError {
+    return match predicate.subject() {
+        Some(path) => EngineError::UnsupportedCondition(path.to_string()),
+        None => EngineError::UnsupportedPredicate
+    };
+}
+
+fn take_key_condition(
+    key_conditions: &mut HashMap<String, Value>,
+    name: &str,
+    key_type: KeyType
+) -> EngineResult<Value> {
+    let value = key_conditions.remove(name).ok_or_else(|| EngineError::MissingKeyAttribute(name.to_string()))?;
+
+    return validate_key_value(name, key_type, value);
+}
+
+fn check_write_condition(
+    condition: Option<&WriteCondition>,
+    existing: Option<&HashMap<String, Value>>,
+    store_name: &str
+) -> PliteDbResult<()> {
+    let holds = match (condition, existing) {
+        (None, _) => true,
+        (Some(WriteCondition::Exists), existing) => existing.is_some(),
+        (Some(WriteCondition::NotExists), existing) => existing.is_none(),
+        (Some(WriteCondition::Predicate(_)), None) => false,
+        (Some(WriteCondition::Predicate(predicate)), Some(attributes)) => evaluate_predicate(predicate, attributes)?
+    };
+
+    if !holds {
+        return Err(EngineError::ConditionalCheckFailed(store_name.to_string()).into());
+    }
+
+    return Ok(());
+}
+
+fn check_schema(
+    store_name: &str,
+    schema: Option<&Schema>,
+    attributes: &mut HashMap<String, Value>,
+    key_names: &[&String]
+) -> EngineResult<()> {
+    let Some(schema) = schema
+    else {
+        return Ok(());
+    };
+
+    return schema::validate(schema, attributes, key_names).map_err(|violation| EngineError::SchemaViolation(store_name.to_string(), violation));
+}
+
+fn reject_remaining_conditions(key_conditions: HashMap<String, Value>) -> EngineResult<()> {
+    return match key_conditions.into_keys().next() {
+        Some(name) => Err(EngineError::UnsupportedCondition(name)),
+        None => Ok(())
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::TimeDelta;
+
+    use crate::{
+        engine::{
+            error::EngineError,
+            evaluator::error::EvaluationError,
+            executor::QueryOutput,
+            model::{Database, Value},
+            testing::{execute, get_item, scan_page, sort_keys, test_database}
+        },
+        error::PliteDbError,
+        query::parser::error::ParserError
+    };
+
+    #[test]
+    fn puts_and_gets_simple_items() {
+        let mut database = test_database();
+
+        execute(&mut database, "PUT users { userId: 'a', name: 'Alice', age: 30 + 1 }").unwrap();
+        execute(&mut database, "PUT users { userId: 'b', name: 'Bob' }").unwrap();
+
+        let item = get_item(&mut database, "GET users WHERE { userId = 'a' }");
+
+        assert_eq!(item.get("name"), Some(&Value::String(String::from("Alice"))));
+        assert_eq!(item.get("age"), Some(&Value::Integer(31)));
+        assert_eq!(
+            execute(&mut database, "GET users WHERE { userId = 'c' }").unwrap(),
+            QueryOutput::Item(None)
+        );
+    }
+
+    #[test]
+    fn puts_and_gets_partitioned_items() {
+        let mut database = test_database();
+
+        execute(&mut database, "PUT events { deviceId: 'a', ts: 2, reading: 1.5 }").unwrap();
+        execute(&mut database, "PUT events { deviceId: 'a', ts: 1, reading: 0.5 }").unwrap();
+        execute(&mut database, "PUT events { deviceId: 'a', ts: 2, reading: 2.5 }").unwrap();
+
+        let item = get_item(&mut database, "GET events WHERE { deviceId = 'a', ts = 2 }");
+
+        assert_eq!(item.get("reading"), Some(&Value::Float(2.5)));
+        assert_eq!(
+            execute(&mut database, "GET events WHERE { deviceId = 'b', ts = 2 }").unwrap(),
+            QueryOutput::Item(None)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_keys_and_conditions() {
+        let mut database = test_database();
+
+        assert!(matches!(
+            execute(&mut database, "PUT missing { id: 1 }"),
+            Err(PliteDbError::EngineError(EngineError::StoreNotFound(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT events { deviceId: 'a' }"),
+            Err(PliteDbError::EngineError(EngineError::MissingKeyAttribute(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 1.5 }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(..)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'a', userId: 'b' }"),
+            Err(PliteDbError::EngineError(EngineError::DuplicateAttribute(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "GET users WHERE { userId > 'a' }"),
+            Err(PliteDbError::EngineError(EngineError::UnsupportedCondition(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "GET users WHERE { userId = 'a', name = 'Alice' }"),
+            Err(PliteDbError::EngineError(EngineError::UnsupportedCondition(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "GET events WHERE { deviceId = 'a' }"),
+            Err(PliteDbError::EngineError(EngineError::MissingKeyAttribute(_)))
+        ));
+    }
+
+    #[test]
+    fn distinguishes_typed_keys() {
+        let mut database = Database::new(String::from("test"));
+        execute(&mut database, "CREATE STORE numbered HASH KEY id INTEGER").unwrap();
+        execute(&mut database, "PUT numbered { id: 5, name: 'five' }").unwrap();
+
+        let item = execute(&mut database, "GET numbered WHERE { id = 5 }").unwrap();
+        assert!(matches!(item, QueryOutput::Item(Some(_))));
+
+        assert!(matches!(
+            execute(&mut database, "GET numbered WHERE { id = '5' }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(_, "integer", "string")))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT numbered { id: '5' }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(_, "integer", "string")))
+        ));
+
+        let mut database = test_database();
+        execute(&mut database, "PUT users { userId: '5' }").unwrap();
+
+        assert!(matches!(
+            execute(&mut database, "GET users WHERE { userId = 5 }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(_, "string", "integer")))
+        ));
+        assert!(matches!(
+            execute(&mut database, "QUERY events WHERE { deviceId = 'a', ts > '1' }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(_, "integer", "string")))
+        ));
+
+        // Keys without a type are strings.
+        execute(&mut database, "CREATE STORE untyped HASH KEY id SORT KEY name").unwrap();
+        execute(&mut database, "PUT untyped { id: 'a', name: 'b' }").unwrap();
+        assert!(matches!(
+            execute(&mut database, "PUT untyped { id: 'a', name: 1 }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(_, "string", "integer")))
+        ));
+    }
+
+    #[test]
+    fn evaluates_boolean_logic_in_conditions() {
+        let mut database = test_database();
+
+        for (user_id, age, vip, banned) in [("a", 40, false, false), ("b", 20, true, false), ("c", 20, false, false), ("d", 50, true, true)] {
+            execute(
+                &mut database,
+                &format!(
+                    "PUT users {{ userId: '{}', age: {}, vip: {}, banned: {} }}",
+                    user_id, age, vip, banned
+                )
+            )
+            .unwrap();
+        }
+
+        execute(&mut database, "PUT users { userId: 'e', age: 60 }").unwrap();
+
+        let (items, _) = scan_page(
+            &mut database,
+            "SCAN users FILTER { (age > 30 OR vip = true) AND NOT banned }",
+            "userId"
+        );
+        assert_eq!(items, ["a", "b", "e"].map(|id| Value::String(id.to_string())));
+
+        let (items, _) = scan_page(&mut database, "SCAN users FILTER { age * 2 > age + 30, vip }", "userId");
+        assert_eq!(items, [Value::String(String::from("d"))]);
+
+        assert!(matches!(
+            execute(&mut database, "SCAN users FILTER { age + 1 }"),
+            Err(PliteDbError::EvaluationError(EvaluationError::NonBooleanCondition("integer")))
+        ));
+
+        assert!(matches!(
+            execute(&mut database, "DELETE users WHERE { userId = 'd' } IF NOT banned OR age < 30"),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+        assert_eq!(
+            execute(
+                &mut database,
+                "DELETE users WHERE { userId = 'd' } IF NOT (banned AND age < 30)"
+            )
+            .unwrap(),
+            QueryOutput::Deleted(1)
+        );
+
+        assert!(matches!(
+            execute(&mut database, "GET users WHERE { userId = 'a' OR userId = 'b' }"),
+            Err(PliteDbError::EngineError(EngineError::UnsupportedPredicate))
+        ));
+        assert!(matches!(
+            execute(&mut database, "QUERY events WHERE { deviceId = 'x' AND NOT ts > 1 }"),
+            Err(PliteDbError::EngineError(EngineError::UnsupportedPredicate))
+        ));
+        assert!(execute(&mut database, "QUERY events WHERE { deviceId = 'x' AND ts > 1 }").is_ok());
+    }
+
+    #[test]
+    fn accepts_lowercase_keywords_and_contextual_words_as_names() {
+        let mut database = test_database();
+
+        execute(
+            &mut database,
+            "put users { userId: 'a', `key`: 'k', `set`: [1, 2] } if not exists"
+        )
+        .unwrap();
+        execute(
+            &mut database,
+            "update users where { userId = 'a' } set { `key`: `key` + '!' } if `set`[1] = 2"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "Get users Select `key` Where { `userId` = 'a' }");
+        assert_eq!(item, HashMap::from([(String::from("key"), Value::String(String::from("k!")))]));
+
+        // Type and clause words are only keywords where the grammar expects them, so they need no quoting
+        // as attribute names, but a quoted name is never taken as a keyword.
+        execute(
+            &mut database,
+            "update users where { userId = 'a' } set { timestamp: 1, exists: true } if key = 'k!' and set[0] = 1"
+        )
+        .unwrap();
+        let item = get_item(&mut database, "GET users SELECT key, timestamp WHERE { userId = 'a' }");
+        assert_eq!(
+            item,
+            HashMap::from([
+                (String::from("key"), Value::String(String::from("k!"))),
+                (String::from("timestamp"), Value::Integer(1))
+            ])
+        );
+        assert_eq!(
+            execute(&mut database, "PUT users { userId: 'a' } IF `exists`").unwrap(),
+            QueryOutput::Written
+        );
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'a' } IF NOT exists"),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+
+        assert!(matches!(
+            execute(&mut database, "GET update SELECT key WHERE { userId = 'a' }"),
+            Err(PliteDbError::ParserError(ParserError::UnexpectedToken(..)))
+        ));
+    }
+
+    #[test]
+    fn distinguishes_null_from_missing_attributes() {
+        let mut database = test_database();
+
+        execute(&mut database, "PUT users { userId: 'a', email: null }").unwrap();
+        execute(&mut database, "PUT users { userId: 'b', email: 'b@example.com' }").unwrap();
+        execute(&mut database, "PUT users { userId: 'c' }").unwrap();
+
+        let (existing, _) = scan_page(&mut database, "SCAN users FILTER { attribute_exists(email) }", "userId");
+        assert_eq!(existing, ["a", "b"].map(|id| Value::String(id.to_string())));
+
+        let (missing, _) = scan_page(&mut database, "SCAN users FILTER { attribute_not_exists(email) }", "userId");
+        assert_eq!(missing, [Value::String(String::from("c"))]);
+
+        let (null, _) = scan_page(&mut database, "SCAN users FILTER { email = null }", "userId");
+        assert_eq!(null, [Value::String(String::from("a"))]);
+
+        let (not_null, _) = scan_page(&mut database, "SCAN users FILTER { email != null }", "userId");
+        assert_eq!(not_null, [Value::String(String::from("b"))]);
+
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'c' } SET { email: 'c@example.com' } IF attribute_not_exists(email)"
+        )
+        .unwrap();
+        assert!(matches!(
+            execute(
+                &mut database,
+                "UPDATE users WHERE { userId = 'a' } SET { email: 'a@example.com' } IF attribute_not_exists(email)"
+            ),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: null }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(_, "string", "null")))
+        ));
+        assert!(matches!(
+            execute(&mut database, "QUERY events WHERE { deviceId = 'a', attribute_exists(ts) }"),
+            Err(PliteDbError::EngineError(EngineError::UnsupportedCondition(_)))
+        ));
+    }
+
+    #[test]
+    fn stores_items_under_binary_keys() {
+        let mut database = test_database();
+        execute(&mut database, "CREATE STORE blobs HASH KEY digest BINARY SORT KEY ts BINARY").unwrap();
+
+        for ts in ["x'0001'", "x'00'", "x'ff'", "x'0100'"] {
+            execute(
+                &mut database,
+                &format!("PUT blobs {{ digest: b64'3q2+7w==', ts: {}, data: x'cafe' }}", ts)
+            )
+            .unwrap();
+        }
+
+        let ordered = execute(&mut database, "QUERY blobs WHERE { digest = x'deadbeef', ts < x'ff' }").unwrap();
+        assert_eq!(
+            sort_keys(ordered),
+            [vec![0x00], vec![0x00, 0x01], vec![0x01, 0x00]].map(Value::Bytes)
+        );
+
+        let (prefixed, _) = scan_page(
+            &mut database,
+            "SCAN blobs FILTER { ts BEGINS_WITH x'00', data = x'CAFE' }",
+            "ts"
+        );
+        assert_eq!(prefixed, [vec![0x00], vec![0x00, 0x01]].map(Value::Bytes));
+
+        execute(
+            &mut database,
+            "PUT users { userId: 'a', avatar: x'89504e47', tags: ['a', 'b'] }"
+        )
+        .unwrap();
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'a' } SET { avatarSize: size(avatar), tagCount: size(tags), nameSize: size('héllo') }"
+        )
+        .unwrap();
+
+        let item = get_item(
+            &mut database,
+            "GET users SELECT avatarSize, tagCount, nameSize WHERE { userId = 'a' }"
+        );
+        assert_eq!(item.get("avatarSize"), Some(&Value::Integer(4)));
+        assert_eq!(item.get("tagCount"), Some(&Value::Integer(2)));
+        assert_eq!(item.get("nameSize"), Some(&Value::Integer(5)));
+
+        assert!(matches!(
+            execute(&mut database, "PUT blobs { digest: 'deadbeef', ts: x'00' }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(_, "bytes", "string")))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'b', n: size(1) }"),
+            Err(PliteDbError::EvaluationError(EvaluationError::InvalidArguments(..)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'b', n: size('a', 'b') }"),
+            Err(PliteDbError::EvaluationError(EvaluationError::InvalidArgumentCount(_, expected, 2, _))) if expected == "1"
+        ));
+    }
+
+    #[test]
+    fn stores_items_under_decimal_keys() {
+        let mut database = test_database();
+        let decimal = |value: &str| Value::Decimal(value.parse().unwrap());
+
+        execute(
+            &mut database,
+            "CREATE STORE invoices HASH KEY customer STRING SORT KEY ts DECIMAL"
+        )
+        .unwrap();
+
+        for ts in ["10.10d", "10.1d", "9.99d", "10.001d", "100d"] {
+            execute(
+                &mut database,
+                &format!("PUT invoices {{ customer: 'c1', ts: {}, total: {} * 3 + 0.01d }}", ts, ts)
+            )
+            .unwrap();
+        }
+
+        // 10.10 and 10.1 are the same key, so the second write replaces the first.
+        let ordered = execute(&mut database, "QUERY invoices WHERE { customer = 'c1', ts < 100d }").unwrap();
+        assert_eq!(sort_keys(ordered), [decimal("9.99"), decimal("10.001"), decimal("10.1")]);
+
+        let item = get_item(&mut database, "GET invoices WHERE { customer = 'c1', ts = 9.990d }");
+        assert_eq!(item.get("total"), Some(&decimal("29.98")));
+
+        assert!(matches!(
+            execute(&mut database, "PUT invoices { customer: 'c1', ts: 9.99 }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(_, "decimal", "float")))
+        ));
+    }
+
+    #[test]
+    fn stores_items_under_timestamp_keys() {
+        let mut database = test_database();
+        execute(
+            &mut database,
+            "CREATE STORE readings HASH KEY sensor STRING SORT KEY ts TIMESTAMP"
+        )
+        .unwrap();
+
+        for ts in [
+            "ts'2024-03-01T10:00:00Z'",
+            "ts'2024-03-01T09:00:00Z'",
+            "ts'2024-03-01T12:00:00+02:00'",
+            "ts'2024-03-02T00:00:00Z'"
+        ] {
+            execute(&mut database, &format!("PUT readings {{ sensor: 's1', ts: {} }}", ts)).unwrap();
+        }
+
+        let morning = execute(
+            &mut database,
+            "QUERY readings WHERE { sensor = 's1', ts BETWEEN ts'2024-03-01T09:00:00Z' AND ts'2024-03-01T09:00:00Z' + dur'1h' }"
+        )
+        .unwrap();
+        // 12:00+02:00 is the same instant as 10:00Z, so it replaces that item.
+        assert_eq!(
+            sort_keys(morning),
+            ["2024-03-01T09:00:00Z", "2024-03-01T10:00:00Z"].map(|ts| Value::Timestamp(ts.parse().unwrap()))
+        );
+
+        execute(
+            &mut database,
+            "PUT users { userId: 'a', joined: ts'2024-03-06T15:45:30.5Z', left: ts'2024-03-08T03:45:30.5Z' }"
+        )
+        .unwrap();
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'a' } SET { stay: left - joined, renewal: joined + dur'30d' * 2, week: date_trunc('week', joined), \
+             month: date_trunc('month', joined), second: date_trunc('second', joined) }"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "GET users WHERE { userId = 'a' }");
+        let timestamp = |ts: &str| Value::Timestamp(ts.parse().unwrap());
+        assert_eq!(item.get("stay"), Some(&Value::Duration(TimeDelta::hours(36))));
+        assert_eq!(item.get("renewal"), Some(&timestamp("2024-05-05T15:45:30.5Z")));
+        assert_eq!(item.get("week"), Some(&timestamp("2024-03-04T00:00:00Z")));
+        assert_eq!(item.get("month"), Some(&timestamp("2024-03-01T00:00:00Z")));
+        assert_eq!(item.get("second"), Some(&timestamp("2024-03-06T15:45:30Z")));
+
+        let recent = execute(&mut database, "SCAN users FILTER { joined < now() - dur'1d' }").unwrap();
+        assert!(matches!(recent, QueryOutput::Page { items, .. } if items.len() == 1));
+
+        assert!(matches!(
+            execute(&mut database, "PUT readings { sensor: 's1', ts: '2024-03-01' }"),
+            Err(PliteDbError::EngineError(EngineError::InvalidKeyValue(
+                _,
+                "timestamp",
+                "string"
+            )))
+        ));
+        assert!(matches!(
+            execute(
+                &mut database,
+                "PUT users { userId: 'b', n: ts'2024-03-01T00:00:00Z' + ts'2024-03-01T00:00:00Z' }"
+            ),
+            Err(PliteDbError::EvaluationError(EvaluationError::InvalidOperands(..)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'b', n: dur'1s' / 0 }"),
+            Err(PliteDbError::EvaluationError(EvaluationError::DivisionByZero))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'b', n: date_trunc('fortnight', now()) }"),
+            Err(PliteDbError::EvaluationError(EvaluationError::UnknownDateUnit(..)))
+        ));
+    }
+}
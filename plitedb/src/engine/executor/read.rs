@@ -0,0 +1,464 @@
+use std::{
+    collections::{BinaryHeap, HashMap},
+    ops::Bound
+};
+
+use crate::{
+    engine::{
+        document,
+        error::EngineError,
+        evaluator::{evaluate, evaluate_predicate},
+        model::{Partition, Store, Value}
+    },
+    error::PliteDbResult,
+    query::parser::ast::{Assignment, AttributePath, ComparisonOperator, KeyType, Predicate}
+};
+
+use super::{
+    evaluate_assignments, evaluate_key_conditions, key_attribute, reject_remaining_conditions, take_key_condition, unsupported_condition, validate_key_value,
+    QueryOutput
+};
+
+pub(super) fn get_item(
+    store: &Store,
+    projection: Option<&[AttributePath]>,
+    predicate: &Predicate
+) -> PliteDbResult<QueryOutput> {
+    let mut key_conditions = evaluate_key_conditions(predicate)?;
+
+    let item = match store {
+        Store::Simple(store) => {
+            let hash_key_value = take_key_condition(&mut key_conditions, &store.hash_key_name, store.hash_key_type)?;
+            reject_remaining_conditions(key_conditions)?;
+
+            store.item_by_hash_key.get(&hash_key_value).map(|item| project(&item.attributes, projection))
+        },
+        Store::Partition(store) => {
+            let hash_key_value = take_key_condition(&mut key_conditions, &store.hash_key_name, store.hash_key_type)?;
+            let sort_key_value = take_key_condition(&mut key_conditions, &store.sort_key_name, store.sort_key_type)?;
+            reject_remaining_conditions(key_conditions)?;
+
+            store
+                .partition_by_hash_key
+                .get(&hash_key_value)
+                .and_then(|partition| partition.get(&sort_key_value))
+                .map(|item| project(&item.attributes, projection))
+        }
+    };
+
+    return Ok(QueryOutput::Item(item));
+}
+
+pub(super) fn query(
+    store: &Store,
+    predicate: &Predicate
+) -> PliteDbResult<QueryOutput> {
+    let Store::Partition(store) = store
+    else {
+        return Err(EngineError::MissingSortKey(store.name().to_string()).into());
+    };
+
+    let mut hash_key_value = None;
+    let mut sort_key_condition = None;
+
+    for condition in predicate.conjuncts() {
+        let Some(path) = condition.subject()
+        else {
+            return Err(unsupported_condition(condition).into());
+        };
+
+        if !path.is_top_level() {
+            return Err(EngineError::UnsupportedCondition(path.to_string()).into());
+        }
+
+        let name = path.name.as_str();
+
+        if name == store.hash_key_name {
+            let Predicate::Comparison {
+                operator: ComparisonOperator::Equal,
+                right: value,
+                ..
+            } = condition
+            else {
+                return Err(EngineError::UnsupportedCondition(name.to_string()).into());
+            };
+
+            if hash_key_value.replace(evaluate(value, None)?).is_some() {
+                return Err(EngineError::DuplicateCondition(name.to_string()).into());
+            }
+        }
+        else if name == store.sort_key_name {
+            if sort_key_condition.replace(condition).is_some() {
+                return Err(EngineError::DuplicateCondition(name.to_string()).into());
+            }
+        }
+        else {
+            return Err(EngineError::UnsupportedCondition(name.to_string()).into());
+        }
+    }
+
+    let hash_key_value = hash_key_value.ok_or_else(|| EngineError::MissingKeyAttribute(store.hash_key_name.clone()))?;
+    let hash_key_value = validate_key_value(&store.hash_key_name, store.hash_key_type, hash_key_value)?;
+
+    let partition = store.partition_by_hash_key.get(&hash_key_value);
+
+    let items = match sort_key_condition {
+        Some(condition) => query_sort_key_range(partition, condition, store.sort_key_type)?,
+        None => partition
+            .into_iter()
+            .flat_map(|partition| partition.items.iter().map(|item| item.attributes.clone()))
+            .collect()
+    };
+
+    return Ok(QueryOutput::Items(items));
+}
+
+// Bounds are checked against the declared sort key type even when the partition is missing, so every
+// item in the range is comparable with them and mistyped queries are rejected consistently.
+fn query_sort_key_range(
+    partition: Option<&Partition>,
+    condition: &Predicate,
+    sort_key_type: KeyType
+) -> PliteDbResult<Vec<HashMap<String, Value>>> {
+    let Some(path) = condition.subject()
+    else {
+        return Err(unsupported_condition(condition).into());
+    };
+
+    let name = path.name.as_str();
+
+    let items = match condition {
+        Predicate::Comparison { operator, right, .. } => {
+            let value = validate_key_value(name, sort_key_type, evaluate(right, None)?)?;
+
+            let (lower, upper) = match operator {
+                ComparisonOperator::Equal => (Bound::Included(&value), Bound::Included(&value)),
+                ComparisonOperator::GreaterThan => (Bound::Excluded(&value), Bound::Unbounded),
+                ComparisonOperator::GreaterThanOrEqual => (Bound::Included(&value), Bound::Unbounded),
+                ComparisonOperator::LessThan => (Bound::Unbounded, Bound::Excluded(&value)),
+                ComparisonOperator::LessThanOrEqual => (Bound::Unbounded, Bound::Included(&value)),
+                ComparisonOperator::NotEqual => return Err(EngineError::UnsupportedCondition(path.to_string()).into())
+            };
+
+            partition
+                .into_iter()
+                .flat_map(|partition| partition.range(lower, upper))
+                .map(|item| item.attributes.clone())
+                .collect()
+        },
+        Predicate::Between { low, high, .. } => {
+            let low = validate_key_value(name, sort_key_type, evaluate(low, None)?)?;
+            let high = validate_key_value(name, sort_key_type, evaluate(high, None)?)?;
+
+            partition
+                .into_iter()
+                .flat_map(|partition| partition.range(Bound::Included(&low), Bound::Included(&high)))
+                .map(|item| item.attributes.clone())
+                .collect()
+        },
+        Predicate::BeginsWith { prefix, .. } => {
+            let prefix = match evaluate(prefix, None)? {
+                Value::String(prefix) => prefix,
+                value => return Err(EngineError::InvalidPrefix(value.type_name()).into())
+            };
+
+            let lower = validate_key_value(name, sort_key_type, Value::String(prefix.clone()))?;
+
+            partition
+                .into_iter()
+                .flat_map(|partition| partition.range(Bound::Included(&lower), Bound::Unbounded))
+                .take_while(|item| matches!(&item.sort_key_value, Value::String(sort_key) if sort_key.starts_with(&prefix)))
+                .map(|item| item.attributes.clone())
+                .collect()
+        },
+        _ => return Err(EngineError::UnsupportedCondition(name.to_string()).into())
+    };
+
+    return Ok(items);
+}
+
+fn project(
+    attributes: &HashMap<String, Value>,
+    projection: Option<&[AttributePath]>
+) -> HashMap<String, Value> {
+    return match projection {
+        Some(paths) => document::project(attributes, paths),
+        None => attributes.clone()
+    };
+}
+
+// The limit counts evaluated items, not matches, so every page does a bounded amount of work. Items
+// are visited in hash key order, and in sort key order within a partition, so that a scan can resume
+// after the last evaluated key.
+pub(super) fn scan(
+    store: &Store,
+    filter: Option<&Predicate>,
+    limit: Option<usize>,
+    start_after: Option<&[Assignment]>
+) -> PliteDbResult<QueryOutput> {
+    let start_after = start_after.map(evaluate_assignments).transpose()?;
+
+    return match store {
+        Store::Simple(store) => {
+            let start = match start_after {
+                Some(key) => Some(key_attribute(&key, &store.hash_key_name, store.hash_key_type)?),
+                None => None
+            };
+
+            let keys = smallest_keys(
+                store.item_by_hash_key.keys().filter(|key| start.as_ref().is_none_or(|start| *key > start)),
+                limit.map(|limit| limit + 1)
+            );
+
+            let items = keys.into_iter().map(|key| &store.item_by_hash_key[key].attributes);
+
+            paginate(items, &[&store.hash_key_name], filter, limit)
+        },
+        Store::Partition(store) => {
+            let start = match start_after {
+                Some(key) => Some((
+                    key_attribute(&key, &store.hash_key_name, store.hash_key_type)?,
+                    key_attribute(&key, &store.sort_key_name, store.sort_key_type)?
+                )),
+                None => None
+            };
+
+            let remainder = start
+                .as_ref()
+                .and_then(|(hash_key_value, sort_key_value)| {
+                    store
+                        .partition_by_hash_key
+                        .get(hash_key_value)
+                        .map(|partition| partition.range(Bound::Excluded(sort_key_value), Bound::Unbounded))
+                })
+                .into_iter()
+                .flatten();
+
+            // Partitions are never empty, so the next limit + 1 partitions hold enough items to fill
+            // the page and tell whether anything is left after it.
+            let keys = smallest_keys(
+                store
+                    .partition_by_hash_key
+                    .keys()
+                    .filter(|key| start.as_ref().is_none_or(|(start, _)| *key > start)),
+                limit.map(|limit| limit + 1)
+            );
+
+            let items = remainder
+                .chain(keys.into_iter().flat_map(|key| store.partition_by_hash_key[key].items.iter()))
+                .map(|item| &item.attributes);
+
+            paginate(items, &[&store.hash_key_name, &store.sort_key_name], filter, limit)
+        }
+    };
+}
+
+fn smallest_keys<'a, K: Ord>(
+    keys: impl Iterator<Item = &'a K>,
+    count: Option<usize>
+) -> Vec<&'a K> {
+    let Some(count) = count
+    else {
+        let mut keys: Vec<&K> = keys.collect();
+        keys.sort();
+
+        return keys;
+    };
+
+    let mut heap = BinaryHeap::with_capacity(count + 1);
+
+    for key in keys {
+        heap.push(key);
+
+        if heap.len() > count {
+            heap.pop();
+        }
+    }
+
+    return heap.into_sorted_vec();
+}
+
+fn paginate<'a>(
+    items: impl Iterator<Item = &'a HashMap<String, Value>>,
+    key_names: &[&String],
+    filter: Option<&Predicate>,
+    limit: Option<usize>
+) -> PliteDbResult<QueryOutput> {
+    let mut items = items.peekable();
+    let mut page = Vec::new();
+    let mut last_evaluated = None;
+
+    for attributes in items.by_ref().take(limit.unwrap_or(usize::MAX)) {
+        if matches_filter(attributes, filter)? {
+            page.push(attributes.clone());
+        }
+
+        last_evaluated = Some(attributes);
+    }
+
+    let last_evaluated_key = match items.peek() {
+        Some(_) => last_evaluated.map(|attributes| key_names.iter().map(|name| ((*name).clone(), attributes[*name].clone())).collect()),
+        None => None
+    };
+
+    return Ok(QueryOutput::Page {
+        items: page,
+        last_evaluated_key
+    });
+}
+
+fn matches_filter(
+    attributes: &HashMap<String, Value>,
+    filter: Option<&Predicate>
+) -> PliteDbResult<bool> {
+    return match filter {
+        Some(filter) => Ok(evaluate_predicate(filter, attributes)?),
+        None => Ok(true)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        engine::{
+            error::EngineError,
+            model::Value,
+            testing::{execute, scan_page, sort_keys, test_database}
+        },
+        error::PliteDbError,
+        query::parser::error::ParserError
+    };
+
+    #[test]
+    fn queries_sort_key_ranges() {
+        let mut database = test_database();
+
+        for ts in [5, 1, 4, 2, 3] {
+            execute(&mut database, &format!("PUT events {{ deviceId: 'a', ts: {} }}", ts)).unwrap();
+        }
+        execute(&mut database, "PUT events { deviceId: 'b', ts: 3 }").unwrap();
+
+        let all = execute(&mut database, "QUERY events WHERE { deviceId = 'a' }").unwrap();
+        assert_eq!(sort_keys(all), [1, 2, 3, 4, 5].map(Value::Integer));
+
+        let between = execute(&mut database, "QUERY events WHERE { deviceId = 'a', ts BETWEEN 2 AND 4 }").unwrap();
+        assert_eq!(sort_keys(between), [2, 3, 4].map(Value::Integer));
+
+        let greater = execute(&mut database, "QUERY events WHERE { ts > 3, deviceId = 'a' }").unwrap();
+        assert_eq!(sort_keys(greater), [4, 5].map(Value::Integer));
+
+        let at_most = execute(&mut database, "QUERY events WHERE { deviceId = 'a', ts <= 2 }").unwrap();
+        assert_eq!(sort_keys(at_most), [1, 2].map(Value::Integer));
+
+        let empty = execute(&mut database, "QUERY events WHERE { deviceId = 'a', ts BETWEEN 4 AND 2 }").unwrap();
+        assert_eq!(sort_keys(empty), []);
+    }
+
+    #[test]
+    fn queries_sort_key_prefixes() {
+        let mut database = test_database();
+        execute(&mut database, "CREATE STORE logs HASH KEY deviceId STRING SORT KEY ts STRING").unwrap();
+
+        for ts in ["2024-12-31", "2025-01-02", "2025-01-01", "2026-01-01"] {
+            execute(&mut database, &format!("PUT logs {{ deviceId: 'a', ts: '{}' }}", ts)).unwrap();
+        }
+
+        let prefixed = execute(&mut database, "QUERY logs WHERE { deviceId = 'a', ts BEGINS_WITH '2025' }").unwrap();
+        assert_eq!(
+            sort_keys(prefixed),
+            ["2025-01-01", "2025-01-02"].map(|ts| Value::String(ts.to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_queries_without_exact_hash_key() {
+        let mut database = test_database();
+
+        assert!(matches!(
+            execute(&mut database, "QUERY events WHERE { ts > 1 }"),
+            Err(PliteDbError::EngineError(EngineError::MissingKeyAttribute(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "QUERY events WHERE { deviceId > 'a' }"),
+            Err(PliteDbError::EngineError(EngineError::UnsupportedCondition(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "QUERY events WHERE { deviceId = 'a', ts > 1, ts < 5 }"),
+            Err(PliteDbError::EngineError(EngineError::DuplicateCondition(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "QUERY users WHERE { userId = 'a' }"),
+            Err(PliteDbError::EngineError(EngineError::MissingSortKey(_)))
+        ));
+    }
+
+    #[test]
+    fn scans_simple_stores_in_pages() {
+        let mut database = test_database();
+
+        for (user_id, age) in [("d", 40), ("a", 20), ("c", 35), ("b", 50), ("e", 10)] {
+            execute(&mut database, &format!("PUT users {{ userId: '{}', age: {} }}", user_id, age)).unwrap();
+        }
+
+        let (all, last_evaluated_key) = scan_page(&mut database, "SCAN users FILTER { age > 30 }", "userId");
+        assert_eq!(all, ["b", "c", "d"].map(|id| Value::String(id.to_string())));
+        assert_eq!(last_evaluated_key, None);
+
+        let (first, last_evaluated_key) = scan_page(&mut database, "SCAN users FILTER { age > 30 } LIMIT 2", "userId");
+        assert_eq!(first, [Value::String(String::from("b"))]);
+        assert_eq!(
+            last_evaluated_key,
+            Some(HashMap::from([(String::from("userId"), Value::String(String::from("b")))]))
+        );
+
+        let (second, last_evaluated_key) = scan_page(
+            &mut database,
+            "SCAN users FILTER { age > 30 } LIMIT 2 AFTER { userId: 'b' }",
+            "userId"
+        );
+        assert_eq!(second, ["c", "d"].map(|id| Value::String(id.to_string())));
+        assert!(last_evaluated_key.is_some());
+
+        let (third, last_evaluated_key) = scan_page(
+            &mut database,
+            "SCAN users FILTER { age > 30 } LIMIT 2 AFTER { userId: 'd' }",
+            "userId"
+        );
+        assert_eq!(third, []);
+        assert_eq!(last_evaluated_key, None);
+    }
+
+    #[test]
+    fn scans_partition_stores_across_partitions() {
+        let mut database = test_database();
+
+        for (device_id, ts) in [("b", 1), ("a", 2), ("a", 1), ("c", 1), ("b", 2)] {
+            execute(
+                &mut database,
+                &format!("PUT events {{ deviceId: '{}', ts: {} }}", device_id, ts)
+            )
+            .unwrap();
+        }
+
+        let (first, last_evaluated_key) = scan_page(&mut database, "SCAN events LIMIT 3", "deviceId");
+        assert_eq!(first, ["a", "a", "b"].map(|id| Value::String(id.to_string())));
+        assert_eq!(
+            last_evaluated_key,
+            Some(HashMap::from([
+                (String::from("deviceId"), Value::String(String::from("b"))),
+                (String::from("ts"), Value::Integer(1))
+            ]))
+        );
+
+        let (second, last_evaluated_key) = scan_page(&mut database, "SCAN events LIMIT 3 AFTER { deviceId: 'b', ts: 1 }", "deviceId");
+        assert_eq!(second, ["b", "c"].map(|id| Value::String(id.to_string())));
+        assert_eq!(last_evaluated_key, None);
+
+        assert!(matches!(
+            execute(&mut database, "SCAN events LIMIT 0"),
+            Err(PliteDbError::ParserError(ParserError::InvalidLimit(_)))
+        ));
+    }
+}
@@ -0,0 +1,611 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    engine::{
+        document,
+        error::{EngineError, EngineResult},
+        evaluator::{apply_binary_operator, evaluate},
+        model::{Partition, PartitionedItem, SimpleItem, Store, Value}
+    },
+    error::PliteDbResult,
+    query::parser::ast::{Assignment, AttributePath, BinaryOperator, Predicate, WriteCondition}
+};
+
+use super::{
+    check_schema, check_write_condition, evaluate_assignments, evaluate_key_conditions, key_attribute, reject_remaining_conditions, take_key_condition,
+    QueryOutput
+};
+
+pub(super) fn put_item(
+    store: &mut Store,
+    assignments: &[Assignment],
+    condition: Option<&WriteCondition>
+) -> PliteDbResult<QueryOutput> {
+    let mut attributes = evaluate_assignments(assignments)?;
+
+    match store {
+        Store::Simple(store) => {
+            let hash_key_value = key_attribute(&attributes, &store.hash_key_name, store.hash_key_type)?;
+
+            let existing = store.item_by_hash_key.get(&hash_key_value).map(|item| &item.attributes);
+
+            check_write_condition(condition, existing, &store.name)?;
+            check_schema(&store.name, store.schema.as_ref(), &mut attributes, &[&store.hash_key_name])?;
+
+            store.item_by_hash_key.insert(hash_key_value.clone(), SimpleItem { hash_key_value, attributes });
+        },
+        Store::Partition(store) => {
+            let hash_key_value = key_attribute(&attributes, &store.hash_key_name, store.hash_key_type)?;
+            let sort_key_value = key_attribute(&attributes, &store.sort_key_name, store.sort_key_type)?;
+
+            let existing = store
+                .partition_by_hash_key
+                .get(&hash_key_value)
+                .and_then(|partition| partition.get(&sort_key_value))
+                .map(|item| &item.attributes);
+
+            check_write_condition(condition, existing, &store.name)?;
+            check_schema(
+                &store.name,
+                store.schema.as_ref(),
+                &mut attributes,
+                &[&store.hash_key_name, &store.sort_key_name]
+            )?;
+
+            store
+                .partition_by_hash_key
+                .entry(hash_key_value.clone())
+                .or_insert_with(|| Partition::new(store.sort_key_name.clone()))
+                .items
+                .replace(PartitionedItem {
+                    hash_key_value,
+                    sort_key_value,
+                    attributes
+                });
+        }
+    }
+
+    return Ok(QueryOutput::Written);
+}
+
+pub(super) fn update_item(
+    store: &mut Store,
+    predicate: &Predicate,
+    assignments: &[Assignment],
+    additions: &[Assignment],
+    deletions: &[Assignment],
+    upsert: bool,
+    condition: Option<&WriteCondition>
+) -> PliteDbResult<QueryOutput> {
+    let mut key_conditions = evaluate_key_conditions(predicate)?;
+
+    match store {
+        Store::Simple(store) => {
+            let hash_key_value = take_key_condition(&mut key_conditions, &store.hash_key_name, store.hash_key_type)?;
+            reject_remaining_conditions(key_conditions)?;
+
+            let existing = store.item_by_hash_key.get(&hash_key_value).map(|item| &item.attributes);
+
+            check_write_condition(condition, existing, &store.name)?;
+
+            let mut attributes = match existing {
+                Some(attributes) => attributes.clone(),
+                None if upsert => HashMap::from([(store.hash_key_name.clone(), hash_key_value.clone())]),
+                None => return Err(EngineError::ItemNotFound(store.name.clone()).into())
+            };
+
+            apply_updates(&mut attributes, assignments, additions, deletions, &[&store.hash_key_name])?;
+            check_schema(&store.name, store.schema.as_ref(), &mut attributes, &[&store.hash_key_name])?;
+
+            store.item_by_hash_key.insert(hash_key_value.clone(), SimpleItem { hash_key_value, attributes });
+        },
+        Store::Partition(store) => {
+            let hash_key_value = take_key_condition(&mut key_conditions, &store.hash_key_name, store.hash_key_type)?;
+            let sort_key_value = take_key_condition(&mut key_conditions, &store.sort_key_name, store.sort_key_type)?;
+            reject_remaining_conditions(key_conditions)?;
+
+            let existing = store
+                .partition_by_hash_key
+                .get(&hash_key_value)
+                .and_then(|partition| partition.get(&sort_key_value));
+
+            check_write_condition(condition, existing.map(|item| &item.attributes), &store.name)?;
+
+            let mut attributes = match existing {
+                Some(item) => item.attributes.clone(),
+                None if upsert => HashMap::from([
+                    (store.hash_key_name.clone(), hash_key_value.clone()),
+                    (store.sort_key_name.clone(), sort_key_value.clone())
+                ]),
+                None => return Err(EngineError::ItemNotFound(store.name.clone()).into())
+            };
+
+            apply_updates(
+                &mut attributes,
+                assignments,
+                additions,
+                deletions,
+                &[&store.hash_key_name, &store.sort_key_name]
+            )?;
+            check_schema(
+                &store.name,
+                store.schema.as_ref(),
+                &mut attributes,
+                &[&store.hash_key_name, &store.sort_key_name]
+            )?;
+
+            store
+                .partition_by_hash_key
+                .entry(hash_key_value.clone())
+                .or_insert_with(|| Partition::new(store.sort_key_name.clone()))
+                .items
+                .replace(PartitionedItem {
+                    hash_key_value,
+                    sort_key_value,
+                    attributes
+                });
+        }
+    }
+
+    return Ok(QueryOutput::Written);
+}
+
+pub(super) fn delete_item(
+    store: &mut Store,
+    predicate: &Predicate,
+    entire_partition: bool,
+    condition: Option<&WriteCondition>
+) -> PliteDbResult<QueryOutput> {
+    let mut key_conditions = evaluate_key_conditions(predicate)?;
+
+    let deleted = match store {
+        Store::Simple(store) if entire_partition => return Err(EngineError::NotPartitioned(store.name.clone()).into()),
+        Store::Simple(store) => {
+            let hash_key_value = take_key_condition(&mut key_conditions, &store.hash_key_name, store.hash_key_type)?;
+            reject_remaining_conditions(key_conditions)?;
+
+            let existing = store.item_by_hash_key.get(&hash_key_value).map(|item| &item.attributes);
+
+            check_write_condition(condition, existing, &store.name)?;
+
+            store.item_by_hash_key.remove(&hash_key_value).map_or(0, |_| 1)
+        },
+        Store::Partition(store) if entire_partition => {
+            let hash_key_value = take_key_condition(&mut key_conditions, &store.hash_key_name, store.hash_key_type)?;
+            reject_remaining_conditions(key_conditions)?;
+
+            store.partition_by_hash_key.remove(&hash_key_value).map_or(0, |partition| partition.items.len())
+        },
+        Store::Partition(store) => {
+            let hash_key_value = take_key_condition(&mut key_conditions, &store.hash_key_name, store.hash_key_type)?;
+            let sort_key_value = take_key_condition(&mut key_conditions, &store.sort_key_name, store.sort_key_type)?;
+            reject_remaining_conditions(key_conditions)?;
+
+            let existing = store
+                .partition_by_hash_key
+                .get(&hash_key_value)
+                .and_then(|partition| partition.get(&sort_key_value))
+                .map(|item| &item.attributes);
+
+            check_write_condition(condition, existing, &store.name)?;
+
+            let Some(partition) = store.partition_by_hash_key.get_mut(&hash_key_value)
+            else {
+                return Ok(QueryOutput::Deleted(0));
+            };
+
+            let deleted = partition.remove(&sort_key_value).map_or(0, |_| 1);
+
+            if partition.items.is_empty() {
+                store.partition_by_hash_key.remove(&hash_key_value);
+            }
+
+            deleted
+        }
+    };
+
+    return Ok(QueryOutput::Deleted(deleted));
+}
+
+#[derive(Clone, Copy)]
+enum UpdateAction {
+    Set,
+    Add,
+    Delete
+}
+
+// Every action is evaluated against the item as it was before the update, and the updates are then
+// applied in order. Callers work on a copy of the item, so a failed update leaves it untouched. An
+// attribute can only be the target of a single action.
+fn apply_updates(
+    attributes: &mut HashMap<String, Value>,
+    assignments: &[Assignment],
+    additions: &[Assignment],
+    deletions: &[Assignment],
+    key_names: &[&String]
+) -> PliteDbResult<()> {
+    let actions = assignments
+        .iter()
+        .map(|assignment| (UpdateAction::Set, assignment))
+        .chain(additions.iter().map(|assignment| (UpdateAction::Add, assignment)))
+        .chain(deletions.iter().map(|assignment| (UpdateAction::Delete, assignment)));
+
+    let mut updates = Vec::new();
+    let mut assigned = HashSet::new();
+
+    for (action, assignment) in actions {
+        let path = &assignment.path;
+
+        if key_names.contains(&&path.name) {
+            return Err(EngineError::KeyAttributeUpdate(path.name.clone()).into());
+        }
+
+        if !assigned.insert(path) {
+            return Err(EngineError::DuplicateAttribute(path.to_string()).into());
+        }
+
+        let value = evaluate(&assignment.value, Some(attributes))?;
+        let current = document::get(attributes, path);
+
+        let update = match (action, current) {
+            (UpdateAction::Set, _) => Some(value),
+            (UpdateAction::Add, current) => Some(add_to_attribute(path, current, value)?),
+            (UpdateAction::Delete, Some(current)) => delete_from_attribute(path, current, value)?,
+            (UpdateAction::Delete, None) => {
+                reject_non_set_operand("DELETE", path, &value)?;
+                continue;
+            }
+        };
+
+        updates.push((path, update));
+    }
+
+    for (path, update) in updates {
+        match update {
+            Some(value) => document::set(attributes, path, value)?,
+            None => {
+                document::remove(attributes, path);
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+// ADD creates a missing attribute, sums numbers and unions sets of the same type.
+fn add_to_attribute(
+    path: &AttributePath,
+    current: Option<&Value>,
+    value: Value
+) -> PliteDbResult<Value> {
+    if !matches!(value, Value::Integer(_) | Value::Float(_) | Value::Decimal(_)) {
+        reject_non_set_operand("ADD", path, &value)?;
+    }
+
+    let Some(current) = current
+    else {
+        return Ok(value);
+    };
+
+    return match (current, value) {
+        (Value::Integer(_) | Value::Float(_) | Value::Decimal(_), value @ (Value::Integer(_) | Value::Float(_) | Value::Decimal(_))) => {
+            Ok(apply_binary_operator(BinaryOperator::Add, current.clone(), value)?)
+        },
+        (Value::StringSet(current), Value::StringSet(value)) => Ok(Value::StringSet(current.union(&value).cloned().collect())),
+        (Value::NumberSet(current), Value::NumberSet(value)) => Ok(Value::NumberSet(current.union(&value).cloned().collect())),
+        (Value::BinarySet(current), Value::BinarySet(value)) => Ok(Value::BinarySet(current.union(&value).cloned().collect())),
+        (current, value) => Err(EngineError::MismatchedUpdateOperands("ADD", path.to_string(), current.type_name(), value.type_name()).into())
+    };
+}
+
+// DELETE removes the elements of a set from a set of the same type. Sets cannot be empty, so the
+// attribute is removed along with its last element.
+fn delete_from_attribute(
+    path: &AttributePath,
+    current: &Value,
+    value: Value
+) -> PliteDbResult<Option<Value>> {
+    reject_non_set_operand("DELETE", path, &value)?;
+
+    let remaining = match (current, value) {
+        (Value::StringSet(current), Value::StringSet(value)) => difference(current, &value).map(Value::StringSet),
+        (Value::NumberSet(current), Value::NumberSet(value)) => difference(current, &value).map(Value::NumberSet),
+        (Value::BinarySet(current), Value::BinarySet(value)) => difference(current, &value).map(Value::BinarySet),
+        (current, value) => {
+            return Err(EngineError::MismatchedUpdateOperands("DELETE", path.to_string(), current.type_name(), value.type_name()).into());
+        }
+    };
+
+    return Ok(remaining);
+}
+
+fn difference<T: Ord + Clone>(
+    current: &BTreeSet<T>,
+    removed: &BTreeSet<T>
+) -> Option<BTreeSet<T>> {
+    let remaining: BTreeSet<T> = current.difference(removed).cloned().collect();
+
+    return match remaining.is_empty() {
+        true => None,
+        false => Some(remaining)
+    };
+}
+
+fn reject_non_set_operand(
+    action: &'static str,
+    path: &AttributePath,
+    value: &Value
+) -> EngineResult<()> {
+    return match value {
+        Value::StringSet(_) | Value::NumberSet(_) | Value::BinarySet(_) => Ok(()),
+        value => Err(EngineError::InvalidUpdateOperand(action, path.to_string(), value.type_name()))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        engine::{
+            error::EngineError,
+            evaluator::error::EvaluationError,
+            executor::QueryOutput,
+            model::Value,
+            testing::{execute, get_item, scan_page, sort_keys, test_database}
+        },
+        error::PliteDbError
+    };
+
+    #[test]
+    fn updates_items_from_current_attributes() {
+        let mut database = test_database();
+
+        execute(&mut database, "PUT users { userId: 'a', name: 'Alice', age: 30 }").unwrap();
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'a' } SET { age: age + 1, name: 'Bob', nextAge: age + 1 }"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "GET users WHERE { userId = 'a' }");
+
+        assert_eq!(item.get("age"), Some(&Value::Integer(31)));
+        assert_eq!(item.get("nextAge"), Some(&Value::Integer(31)));
+        assert_eq!(item.get("name"), Some(&Value::String(String::from("Bob"))));
+    }
+
+    #[test]
+    fn updates_atomically_and_requires_existing_items() {
+        let mut database = test_database();
+
+        execute(&mut database, "PUT events { deviceId: 'a', ts: 1, count: 1 }").unwrap();
+
+        assert!(matches!(
+            execute(
+                &mut database,
+                "UPDATE events WHERE { deviceId = 'a', ts = 1 } SET { count: count + 1, total: missing + 1 }"
+            ),
+            Err(PliteDbError::EvaluationError(_))
+        ));
+        assert!(matches!(
+            execute(&mut database, "UPDATE events WHERE { deviceId = 'a', ts = 1 } SET { ts: 2 }"),
+            Err(PliteDbError::EngineError(EngineError::KeyAttributeUpdate(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "UPDATE events WHERE { deviceId = 'a', ts = 2 } SET { count: 1 }"),
+            Err(PliteDbError::EngineError(EngineError::ItemNotFound(_)))
+        ));
+
+        let item = get_item(&mut database, "GET events WHERE { deviceId = 'a', ts = 1 }");
+        assert_eq!(item.get("count"), Some(&Value::Integer(1)));
+
+        execute(&mut database, "UPSERT events WHERE { deviceId = 'a', ts = 2 } SET { count: 1 }").unwrap();
+
+        let item = get_item(&mut database, "GET events WHERE { deviceId = 'a', ts = 2 }");
+        assert_eq!(item.get("count"), Some(&Value::Integer(1)));
+        assert_eq!(item.get("ts"), Some(&Value::Integer(2)));
+    }
+
+    #[test]
+    fn deletes_items_and_partitions() {
+        let mut database = test_database();
+
+        execute(&mut database, "PUT users { userId: 'a' }").unwrap();
+        for ts in 1..=3 {
+            execute(&mut database, &format!("PUT events {{ deviceId: 'a', ts: {} }}", ts)).unwrap();
+        }
+
+        assert_eq!(
+            execute(&mut database, "DELETE users WHERE { userId = 'a' }").unwrap(),
+            QueryOutput::Deleted(1)
+        );
+        assert_eq!(
+            execute(&mut database, "DELETE users WHERE { userId = 'a' }").unwrap(),
+            QueryOutput::Deleted(0)
+        );
+        assert_eq!(
+            execute(&mut database, "DELETE events WHERE { deviceId = 'a', ts = 2 }").unwrap(),
+            QueryOutput::Deleted(1)
+        );
+
+        let remaining = execute(&mut database, "QUERY events WHERE { deviceId = 'a' }").unwrap();
+        assert_eq!(sort_keys(remaining), [1, 3].map(Value::Integer));
+
+        assert!(matches!(
+            execute(&mut database, "DELETE events WHERE { deviceId = 'a' }"),
+            Err(PliteDbError::EngineError(EngineError::MissingKeyAttribute(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "DELETE PARTITION users WHERE { userId = 'a' }"),
+            Err(PliteDbError::EngineError(EngineError::NotPartitioned(name))) if name == "users"
+        ));
+        assert_eq!(
+            execute(&mut database, "DELETE PARTITION events WHERE { deviceId = 'a' }").unwrap(),
+            QueryOutput::Deleted(2)
+        );
+        assert_eq!(
+            sort_keys(execute(&mut database, "QUERY events WHERE { deviceId = 'a' }").unwrap()),
+            []
+        );
+    }
+
+    #[test]
+    fn applies_conditional_writes() {
+        let mut database = test_database();
+
+        execute(&mut database, "PUT users { userId: 'a', version: 1 } IF NOT EXISTS").unwrap();
+
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'a', version: 5 } IF NOT EXISTS"),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+        assert!(matches!(
+            execute(
+                &mut database,
+                "UPDATE users WHERE { userId = 'a' } SET { version: version + 1 } IF version = 3"
+            ),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+        assert!(matches!(
+            execute(
+                &mut database,
+                "UPSERT users WHERE { userId = 'b' } SET { version: 1 } IF EXISTS"
+            ),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "DELETE users WHERE { userId = 'a' } IF version > 1"),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'a' } SET { version: version + 1 } IF version = 1.0, version BETWEEN 0 AND 2"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "GET users WHERE { userId = 'a' }");
+        assert_eq!(item.get("version"), Some(&Value::Integer(2)));
+
+        assert_eq!(
+            execute(&mut database, "DELETE users WHERE { userId = 'a' } IF EXISTS").unwrap(),
+            QueryOutput::Deleted(1)
+        );
+
+        execute(&mut database, "PUT events { deviceId: 'a', ts: 1, version: 1 } IF NOT EXISTS").unwrap();
+
+        assert!(matches!(
+            execute(&mut database, "PUT events { deviceId: 'a', ts: 1, version: 5 } IF NOT EXISTS"),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+        assert!(matches!(
+            execute(
+                &mut database,
+                "UPDATE events WHERE { deviceId = 'a', ts = 1 } SET { version: 2 } IF version = 3"
+            ),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+        assert!(matches!(
+            execute(
+                &mut database,
+                "UPSERT events WHERE { deviceId = 'a', ts = 2 } SET { version: 1 } IF EXISTS"
+            ),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "DELETE events WHERE { deviceId = 'a', ts = 1 } IF version > 1"),
+            Err(PliteDbError::EngineError(EngineError::ConditionalCheckFailed(_)))
+        ));
+
+        execute(
+            &mut database,
+            "UPDATE events WHERE { deviceId = 'a', ts = 1 } SET { version: version + 1 } IF version = 1"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "GET events WHERE { deviceId = 'a', ts = 1 }");
+        assert_eq!(item.get("version"), Some(&Value::Integer(2)));
+        assert_eq!(
+            execute(&mut database, "GET events WHERE { deviceId = 'a', ts = 2 }").unwrap(),
+            QueryOutput::Item(None)
+        );
+    }
+
+    #[test]
+    fn updates_sets_with_add_and_delete() {
+        let mut database = test_database();
+        let strings = |elements: &[&str]| Value::StringSet(elements.iter().map(|element| element.to_string()).collect());
+
+        execute(
+            &mut database,
+            "PUT users { userId: 'a', tags: <<'a', 'b', 'a'>>, scores: <<1, 2.5>> }"
+        )
+        .unwrap();
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'a' } ADD tags <<'c'>>, visits 1, scores <<1.0, 3>>"
+        )
+        .unwrap();
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'a' } SET { name: 'Alice' } ADD visits 2 DELETE tags <<'b', 'z'>>"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "GET users WHERE { userId = 'a' }");
+        assert_eq!(item.get("tags"), Some(&strings(&["a", "c"])));
+        assert_eq!(item.get("visits"), Some(&Value::Integer(3)));
+        assert_eq!(
+            item.get("scores"),
+            Some(&Value::NumberSet(
+                [Value::Integer(1), Value::Float(2.5), Value::Integer(3)].into()
+            ))
+        );
+
+        let (tagged, _) = scan_page(
+            &mut database,
+            "SCAN users FILTER { contains(tags, 'c'), contains(scores, 2.5), contains(name, 'lic') }",
+            "userId"
+        );
+        assert_eq!(tagged, [Value::String(String::from("a"))]);
+
+        execute(
+            &mut database,
+            "UPDATE users WHERE { userId = 'a' } DELETE tags <<'a', 'c'>>, missing <<'x'>>"
+        )
+        .unwrap();
+
+        let item = get_item(&mut database, "GET users WHERE { userId = 'a' }");
+        assert_eq!(item.get("tags"), None);
+
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'b', tags: <<>> }"),
+            Err(PliteDbError::EvaluationError(EvaluationError::EmptySet))
+        ));
+        assert!(matches!(
+            execute(&mut database, "PUT users { userId: 'b', tags: <<'a', 1>> }"),
+            Err(PliteDbError::EvaluationError(EvaluationError::InvalidSetElement(
+                "string set",
+                "integer"
+            )))
+        ));
+        assert!(matches!(
+            execute(&mut database, "UPDATE users WHERE { userId = 'a' } ADD scores <<'a'>>"),
+            Err(PliteDbError::EngineError(EngineError::MismatchedUpdateOperands("ADD", ..)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "UPDATE users WHERE { userId = 'a' } ADD name 'x'"),
+            Err(PliteDbError::EngineError(EngineError::InvalidUpdateOperand("ADD", ..)))
+        ));
+        assert!(matches!(
+            execute(
+                &mut database,
+                "UPDATE users WHERE { userId = 'a' } SET { visits: 0 } ADD visits 1"
+            ),
+            Err(PliteDbError::EngineError(EngineError::DuplicateAttribute(_)))
+        ));
+        assert!(matches!(
+            execute(&mut database, "UPDATE users WHERE { userId = 'a' } IF EXISTS"),
+            Err(PliteDbError::ParserError(_))
+        ));
+    }
+}
@@ -1 +1,11 @@
+pub mod catalog;
+pub mod document;
+pub mod error;
+pub mod evaluator;
+pub mod executor;
 pub mod model;
+pub mod schema;
+pub mod transaction;
+
+#[cfg(test)]
+mod testing;
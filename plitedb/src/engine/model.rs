@@ -1,55 +1,544 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    hash::{Hash, Hasher},
+    ops::Bound
+};
 
-#[derive(Debug, PartialEq, PartialOrd)]
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::query::parser::ast::{self, KeyType, Schema};
+
+#[derive(Debug, Clone)]
 pub enum Value {
     Integer(i64),
     Float(f64),
+    Decimal(BigDecimal),
     String(String),
-    Boolean(bool)
+    Boolean(bool),
+    Bytes(Vec<u8>),
+    Timestamp(DateTime<Utc>),
+    Duration(TimeDelta),
+    Null,
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+    StringSet(BTreeSet<String>),
+    // Only holds integers, floats and decimals.
+    NumberSet(BTreeSet<Value>),
+    BinarySet(BTreeSet<Vec<u8>>)
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        return match self {
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Decimal(_) => "decimal",
+            Value::String(_) => "string",
+            Value::Boolean(_) => "boolean",
+            Value::Bytes(_) => "bytes",
+            Value::Timestamp(_) => "timestamp",
+            Value::Duration(_) => "duration",
+            Value::Null => "null",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+            Value::StringSet(_) => "string set",
+            Value::NumberSet(_) => "number set",
+            Value::BinarySet(_) => "binary set"
+        };
+    }
+
+    fn rank(&self) -> u8 {
+        return match self {
+            Value::Null => 0,
+            Value::Boolean(_) => 1,
+            Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => 2,
+            Value::Timestamp(_) => 3,
+            Value::Duration(_) => 4,
+            Value::String(_) => 5,
+            Value::Bytes(_) => 6,
+            Value::List(_) => 7,
+            Value::Map(_) => 8,
+            Value::StringSet(_) => 9,
+            Value::NumberSet(_) => 10,
+            Value::BinarySet(_) => 11
+        };
+    }
+}
+
+// Values are totally ordered so that they can be used as hash keys and sort keys. Null sorts first,
+// then booleans, numbers, timestamps, durations, strings, bytes, lists, maps and the string, number
+// and binary sets. Bytes compare lexicographically as unsigned octets, lists element by element, and
+// maps and sets entry by entry in key order. Integers, floats and decimals are compared by their
+// exact numerical value, so `1`, `1.0` and `1.00d` are the same key, and `0.0` equals `-0.0`. NaN
+// equals itself and sorts after every other number, including infinity.
+impl PartialEq for Value {
+    fn eq(
+        &self,
+        other: &Self
+    ) -> bool {
+        return self.cmp(other) == Ordering::Equal;
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(
+        &self,
+        other: &Self
+    ) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for Value {
+    fn cmp(
+        &self,
+        other: &Self
+    ) -> Ordering {
+        return match (self, other) {
+            (Value::Integer(left), Value::Integer(right)) => left.cmp(right),
+            (Value::Integer(left), Value::Float(right)) => compare_integer_to_float(*left, *right),
+            (Value::Float(left), Value::Integer(right)) => compare_integer_to_float(*right, *left).reverse(),
+            (Value::Float(left), Value::Float(right)) => compare_floats(*left, *right),
+            (Value::Decimal(left), Value::Decimal(right)) => left.cmp(right),
+            (Value::Decimal(left), Value::Integer(right)) => left.cmp(&BigDecimal::from(*right)),
+            (Value::Integer(left), Value::Decimal(right)) => BigDecimal::from(*left).cmp(right),
+            (Value::Decimal(left), Value::Float(right)) => compare_decimal_to_float(left, *right),
+            (Value::Float(left), Value::Decimal(right)) => compare_decimal_to_float(right, *left).reverse(),
+            (Value::String(left), Value::String(right)) => left.cmp(right),
+            (Value::Boolean(left), Value::Boolean(right)) => left.cmp(right),
+            (Value::Bytes(left), Value::Bytes(right)) => left.cmp(right),
+            (Value::Timestamp(left), Value::Timestamp(right)) => left.cmp(right),
+            (Value::Duration(left), Value::Duration(right)) => left.cmp(right),
+            (Value::List(left), Value::List(right)) => left.cmp(right),
+            (Value::Map(left), Value::Map(right)) => left.cmp(right),
+            (Value::StringSet(left), Value::StringSet(right)) => left.cmp(right),
+            (Value::NumberSet(left), Value::NumberSet(right)) => left.cmp(right),
+            (Value::BinarySet(left), Value::BinarySet(right)) => left.cmp(right),
+            (left, right) => left.rank().cmp(&right.rank())
+        };
+    }
+}
+
+// Equal values must hash equally, so integral floats and decimals hash like the integer they equal,
+// decimals that a float represents exactly hash like that float, and every NaN hashes the same.
+impl Hash for Value {
+    fn hash<H: Hasher>(
+        &self,
+        state: &mut H
+    ) {
+        self.rank().hash(state);
+
+        match self {
+            Value::Integer(value) => value.hash(state),
+            Value::Float(value) => match float_to_integer(*value) {
+                Some(value) => value.hash(state),
+                None if value.is_nan() => f64::NAN.to_bits().hash(state),
+                None => value.to_bits().hash(state)
+            },
+            Value::Decimal(value) => match (value.to_i64(), value.to_f64()) {
+                (Some(integer), _) if value.is_integer() => integer.hash(state),
+                (_, Some(float)) if BigDecimal::try_from(float).is_ok_and(|exact| exact == *value) => float.to_bits().hash(state),
+                _ => value.hash(state)
+            },
+            Value::String(value) => value.hash(state),
+            Value::Boolean(value) => value.hash(state),
+            Value::Bytes(value) => value.hash(state),
+            Value::Timestamp(value) => value.hash(state),
+            Value::Duration(value) => value.hash(state),
+            Value::Null => (),
+            Value::List(value) => value.hash(state),
+            Value::Map(value) => value.hash(state),
+            Value::StringSet(value) => value.hash(state),
+            Value::NumberSet(value) => value.hash(state),
+            Value::BinarySet(value) => value.hash(state)
+        }
+    }
+}
+
+// 2^63 is exactly representable as a float, unlike i64::MAX.
+const INTEGER_UPPER_BOUND: f64 = 9_223_372_036_854_775_808.0;
+
+fn compare_floats(
+    left: f64,
+    right: f64
+) -> Ordering {
+    return match (left.is_nan(), right.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+    };
 }
 
-#[derive(Debug)]
+// Casting the integer to a float would round it, so the float is split into its integral part, which
+// fits in an i64 once out-of-range floats are ruled out, and its fractional part.
+fn compare_integer_to_float(
+    integer: i64,
+    float: f64
+) -> Ordering {
+    if float.is_nan() || float >= INTEGER_UPPER_BOUND {
+        return Ordering::Less;
+    }
+
+    if float < -INTEGER_UPPER_BOUND {
+        return Ordering::Greater;
+    }
+
+    let integral = float.trunc();
+
+    return integer.cmp(&(integral as i64)).then_with(|| compare_floats(0.0, float - integral));
+}
+
+// Every finite float has an exact decimal representation. NaN and infinity sort after every decimal,
+// and negative infinity before.
+fn compare_decimal_to_float(
+    decimal: &BigDecimal,
+    float: f64
+) -> Ordering {
+    return match BigDecimal::try_from(float) {
+        Ok(float) => decimal.cmp(&float),
+        Err(_) if float == f64::NEG_INFINITY => Ordering::Greater,
+        Err(_) => Ordering::Less
+    };
+}
+
+fn float_to_integer(value: f64) -> Option<i64> {
+    let is_integral = value.fract() == 0.0 && (-INTEGER_UPPER_BOUND..INTEGER_UPPER_BOUND).contains(&value);
+
+    return is_integral.then_some(value as i64);
+}
+
+impl From<&ast::Value> for Value {
+    fn from(value: &ast::Value) -> Self {
+        return match value {
+            ast::Value::Integer(value) => Value::Integer(*value),
+            ast::Value::Float(value) => Value::Float(*value),
+            ast::Value::Decimal(value) => Value::Decimal(value.clone()),
+            ast::Value::String(value) => Value::String(value.clone()),
+            ast::Value::Boolean(value) => Value::Boolean(*value),
+            ast::Value::Bytes(value) => Value::Bytes(value.clone()),
+            ast::Value::Timestamp(value) => Value::Timestamp(*value),
+            ast::Value::Duration(value) => Value::Duration(*value),
+            ast::Value::Null => Value::Null
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct SimpleItem {
     pub hash_key_value: Value,
     pub attributes: HashMap<String, Value>
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SimpleStore {
     pub name: String,
     pub hash_key_name: String,
-    pub item_by_hash_key: HashSet<String, SimpleItem>
+    pub hash_key_type: KeyType,
+    pub schema: Option<Schema>,
+    pub item_by_hash_key: HashMap<Value, SimpleItem>
+}
+
+impl SimpleStore {
+    pub fn new(
+        name: String,
+        hash_key_name: String,
+        hash_key_type: KeyType,
+        schema: Option<Schema>
+    ) -> Self {
+        return SimpleStore {
+            name,
+            hash_key_name,
+            hash_key_type,
+            schema,
+            item_by_hash_key: HashMap::new()
+        };
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PartitionedItem {
     pub hash_key_value: Value,
     pub sort_key_value: Value,
     pub attributes: HashMap<String, Value>
 }
 
-#[derive(Debug)]
+// Items within a partition are identified and ordered by their sort key alone, which lets a partition
+// be looked up by sort key value directly.
+impl Borrow<Value> for PartitionedItem {
+    fn borrow(&self) -> &Value {
+        return &self.sort_key_value;
+    }
+}
+
+impl PartialEq for PartitionedItem {
+    fn eq(
+        &self,
+        other: &Self
+    ) -> bool {
+        return self.sort_key_value == other.sort_key_value;
+    }
+}
+
+impl Eq for PartitionedItem {}
+
+impl PartialOrd for PartitionedItem {
+    fn partial_cmp(
+        &self,
+        other: &Self
+    ) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for PartitionedItem {
+    fn cmp(
+        &self,
+        other: &Self
+    ) -> Ordering {
+        return self.sort_key_value.cmp(&other.sort_key_value);
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Partition {
     pub sort_key_name: String,
     pub items: BTreeSet<PartitionedItem>
 }
 
-#[derive(Debug)]
+impl Partition {
+    pub fn new(sort_key_name: String) -> Self {
+        return Partition {
+            sort_key_name,
+            items: BTreeSet::new()
+        };
+    }
+
+    pub fn get(
+        &self,
+        sort_key_value: &Value
+    ) -> Option<&PartitionedItem> {
+        return self.items.get(sort_key_value);
+    }
+
+    pub fn remove(
+        &mut self,
+        sort_key_value: &Value
+    ) -> Option<PartitionedItem> {
+        return self.items.take(sort_key_value);
+    }
+
+    pub fn range(
+        &self,
+        lower: Bound<&Value>,
+        upper: Bound<&Value>
+    ) -> impl Iterator<Item = &PartitionedItem> {
+        let is_empty = match (lower, upper) {
+            (Bound::Included(lower), Bound::Included(upper)) => lower > upper,
+            (Bound::Included(lower) | Bound::Excluded(lower), Bound::Included(upper) | Bound::Excluded(upper)) => lower >= upper,
+            _ => false
+        };
+
+        let range = (!is_empty).then(|| self.items.range::<Value, _>((lower, upper)));
+
+        return range.into_iter().flatten();
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct PartitionStore {
     pub name: String,
     pub hash_key_name: String,
+    pub hash_key_type: KeyType,
     pub sort_key_name: String,
-    pub partition_by_hash_key: HashMap<String, Partition>
+    pub sort_key_type: KeyType,
+    pub schema: Option<Schema>,
+    pub partition_by_hash_key: HashMap<Value, Partition>
+}
+
+impl PartitionStore {
+    pub fn new(
+        name: String,
+        hash_key_name: String,
+        hash_key_type: KeyType,
+        sort_key_name: String,
+        sort_key_type: KeyType,
+        schema: Option<Schema>
+    ) -> Self {
+        return PartitionStore {
+            name,
+            hash_key_name,
+            hash_key_type,
+            sort_key_name,
+            sort_key_type,
+            schema,
+            partition_by_hash_key: HashMap::new()
+        };
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Store {
     Simple(SimpleStore),
     Partition(PartitionStore)
 }
 
-#[derive(Debug)]
+impl Store {
+    pub fn name(&self) -> &str {
+        return match self {
+            Store::Simple(store) => &store.name,
+            Store::Partition(store) => &store.name
+        };
+    }
+
+    pub fn schema(&self) -> Option<&Schema> {
+        return match self {
+            Store::Simple(store) => store.schema.as_ref(),
+            Store::Partition(store) => store.schema.as_ref()
+        };
+    }
+
+    pub fn key_names(&self) -> Vec<&String> {
+        return match self {
+            Store::Simple(store) => vec![&store.hash_key_name],
+            Store::Partition(store) => vec![&store.hash_key_name, &store.sort_key_name]
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Database {
     pub name: String,
-    pub stores: Vec<Store>
+    pub store_by_name: HashMap<String, Store>,
+    // The stores as they were when the open transaction began, restored on rollback.
+    pub(crate) snapshot: Option<HashMap<String, Store>>
+}
+
+impl Database {
+    pub fn new(name: String) -> Self {
+        return Database {
+            name,
+            store_by_name: HashMap::new(),
+            snapshot: None
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{BuildHasher, RandomState};
+
+    use super::*;
+
+    #[test]
+    fn test_value_order() {
+        let decimal = |value: &str| Value::Decimal(value.parse().unwrap());
+
+        let ordered = [
+            Value::Null,
+            Value::Boolean(false),
+            Value::Boolean(true),
+            Value::Float(f64::NEG_INFINITY),
+            Value::Integer(i64::MIN),
+            Value::Float(-1.5),
+            decimal("-1.25"),
+            Value::Integer(-1),
+            Value::Float(-0.5),
+            Value::Integer(0),
+            decimal("0.1"),
+            Value::Float(0.1),
+            Value::Float(0.5),
+            decimal("0.50000000000000000001"),
+            Value::Integer(i64::MAX),
+            decimal("9223372036854775807.5"),
+            Value::Float(9_223_372_036_854_775_808.0),
+            decimal("1e400"),
+            Value::Float(f64::INFINITY),
+            Value::Float(f64::NAN),
+            Value::Timestamp(DateTime::UNIX_EPOCH),
+            Value::Timestamp(DateTime::UNIX_EPOCH + TimeDelta::microseconds(1)),
+            Value::Duration(TimeDelta::seconds(-1)),
+            Value::Duration(TimeDelta::zero()),
+            Value::String(String::new()),
+            Value::String(String::from("a")),
+            Value::String(String::from("b")),
+            Value::Bytes(vec![]),
+            Value::Bytes(vec![0x00, 0xff]),
+            Value::Bytes(vec![0x01]),
+            Value::List(vec![Value::Integer(1)]),
+            Value::List(vec![Value::Float(1.5)]),
+            Value::List(vec![Value::Integer(2), Value::Integer(0)]),
+            Value::Map(BTreeMap::from([(String::from("a"), Value::Integer(2))])),
+            Value::Map(BTreeMap::from([(String::from("b"), Value::Integer(1))])),
+            Value::StringSet(BTreeSet::from([String::from("a"), String::from("b")])),
+            Value::StringSet(BTreeSet::from([String::from("b")])),
+            Value::NumberSet(BTreeSet::from([Value::Integer(1), Value::Float(2.5)])),
+            Value::NumberSet(BTreeSet::from([Value::Float(1.5)])),
+            Value::BinarySet(BTreeSet::from([vec![0x00]]))
+        ];
+
+        for (index, left) in ordered.iter().enumerate() {
+            for (other_index, right) in ordered.iter().enumerate() {
+                assert_eq!(left.cmp(right), index.cmp(&other_index), "{left:?} vs {right:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_value_equality_and_hash() {
+        let hasher = RandomState::new();
+        let decimal = |value: &str| Value::Decimal(value.parse().unwrap());
+
+        let equal_pairs = [
+            (Value::Integer(1), Value::Float(1.0)),
+            (Value::Float(0.0), Value::Float(-0.0)),
+            (Value::Integer(0), Value::Float(-0.0)),
+            (Value::Float(f64::NAN), Value::Float(-f64::NAN)),
+            (Value::Integer(i64::MIN), Value::Float(-9_223_372_036_854_775_808.0)),
+            (Value::Integer(1), decimal("1.00")),
+            (Value::Float(2.5), decimal("2.50")),
+            (Value::Float(1e20), decimal("100000000000000000000")),
+            (decimal("0.10"), decimal("0.1"))
+        ];
+
+        for (left, right) in equal_pairs {
+            assert_eq!(left, right);
+            assert_eq!(hasher.hash_one(&left), hasher.hash_one(&right));
+        }
+
+        assert_ne!(Value::Integer(i64::MAX), Value::Float(9_223_372_036_854_775_808.0));
+        assert_ne!(Value::Float(0.1), decimal("0.1"));
+        assert_ne!(Value::Integer(1), Value::String(String::from("1")));
+        assert_ne!(Value::Integer(1), Value::Boolean(true));
+    }
+
+    #[test]
+    fn test_partition_lookup() {
+        let mut partition = Partition::new(String::from("ts"));
+
+        for sort_key in [3, 1, 2] {
+            partition.items.insert(PartitionedItem {
+                hash_key_value: Value::String(String::from("a")),
+                sort_key_value: Value::Integer(sort_key),
+                attributes: HashMap::new()
+            });
+        }
+
+        assert!(partition.get(&Value::Float(2.0)).is_some());
+        assert!(partition.get(&Value::Integer(4)).is_none());
+
+        let sort_keys: Vec<&Value> = partition
+            .range(Bound::Excluded(&Value::Float(1.5)), Bound::Unbounded)
+            .map(|item| &item.sort_key_value)
+            .collect();
+
+        assert_eq!(sort_keys, [&Value::Integer(2), &Value::Integer(3)]);
+        assert!(partition.remove(&Value::Integer(1)).is_some());
+        assert_eq!(partition.items.len(), 2);
+    }
 }
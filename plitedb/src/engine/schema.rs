@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::query::parser::ast::{AttributeType, Schema};
+
+use super::{error::SchemaViolation, model::Value};
+
+// Declared attributes are checked in declaration order, so the first violation reported is stable.
+// Key attributes are validated against their key definitions instead. An integer given for a float
+// attribute is widened to a float, as arithmetic promotes it, so stored values match their declaration.
+pub fn validate(
+    schema: &Schema,
+    attributes: &mut HashMap<String, Value>,
+    key_names: &[&String]
+) -> Result<(), SchemaViolation> {
+    for definition in &schema.attributes {
+        match attributes.get(&definition.name) {
+            None if definition.not_null => return Err(SchemaViolation::MissingAttribute(definition.name.clone())),
+            Some(Value::Null) if definition.not_null => return Err(SchemaViolation::NullAttribute(definition.name.clone())),
+            None | Some(Value::Null) => (),
+            Some(Value::Integer(integer)) if definition.attribute_type == AttributeType::Float => {
+                let float = *integer as f64;
+                attributes.insert(definition.name.clone(), Value::Float(float));
+            },
+            Some(value) if !matches_type(definition.attribute_type, value) => {
+                return Err(SchemaViolation::InvalidType(
+                    definition.name.clone(),
+                    definition.attribute_type.name(),
+                    value.type_name()
+                ));
+            },
+            Some(_) => ()
+        }
+    }
+
+    if schema.lenient {
+        return Ok(());
+    }
+
+    let undeclared = attributes
+        .keys()
+        .filter(|name| !key_names.contains(name) && !schema.attributes.iter().any(|definition| definition.name == **name))
+        .min();
+
+    return match undeclared {
+        Some(name) => Err(SchemaViolation::UndeclaredAttribute(name.clone())),
+        None => Ok(())
+    };
+}
+
+fn matches_type(
+    attribute_type: AttributeType,
+    value: &Value
+) -> bool {
+    return matches!(
+        (attribute_type, value),
+        (AttributeType::Integer, Value::Integer(_))
+            | (AttributeType::Float, Value::Float(_))
+            | (AttributeType::Decimal, Value::Decimal(_))
+            | (AttributeType::String, Value::String(_))
+            | (AttributeType::Boolean, Value::Boolean(_))
+            | (AttributeType::Binary, Value::Bytes(_))
+            | (AttributeType::Timestamp, Value::Timestamp(_))
+            | (AttributeType::Duration, Value::Duration(_))
+            | (AttributeType::List, Value::List(_))
+            | (AttributeType::Map, Value::Map(_))
+            | (AttributeType::StringSet, Value::StringSet(_))
+            | (AttributeType::NumberSet, Value::NumberSet(_))
+            | (AttributeType::BinarySet, Value::BinarySet(_))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        engine::{
+            error::{EngineError, SchemaViolation},
+            executor::QueryOutput,
+            model::Value,
+            testing::{execute, get_item, test_database}
+        },
+        error::{PliteDbError, PliteDbResult}
+    };
+
+    #[test]
+    fn validates_items_against_store_schemas() {
+        let mut database = test_database();
+        let violation = |result: PliteDbResult<QueryOutput>| match result {
+            Err(PliteDbError::EngineError(EngineError::SchemaViolation(_, violation))) => violation,
+            result => panic!("expected a schema violation, found {result:?}")
+        };
+
+        execute(
+            &mut database,
+            "CREATE STORE people HASH KEY id STRING WITH SCHEMA { age: INTEGER NOT NULL, email: STRING, tags: STRING SET }"
+        )
+        .unwrap();
+        execute(
+            &mut database,
+            "CREATE STORE metrics HASH KEY id STRING SORT KEY ts INTEGER WITH LENIENT SCHEMA { value: FLOAT NOT NULL }"
+        )
+        .unwrap();
+
+        execute(&mut database, "PUT people { id: 'a', age: 30, email: null, tags: <<'x'>> }").unwrap();
+        execute(
+            &mut database,
+            "UPDATE people WHERE { id = 'a' } SET { age: age + 1 } ADD tags <<'y'>>"
+        )
+        .unwrap();
+        execute(&mut database, "PUT metrics { id: 'm', ts: 1, value: 0.5, unit: 'ms' }").unwrap();
+
+        assert!(matches!(
+            violation(execute(&mut database, "PUT people { id: 'b', agee: 30 }")),
+            SchemaViolation::MissingAttribute(name) if name == "age"
+        ));
+        assert!(matches!(
+            violation(execute(&mut database, "PUT people { id: 'b', age: 30, agee: 30 }")),
+            SchemaViolation::UndeclaredAttribute(name) if name == "agee"
+        ));
+        assert!(matches!(
+            violation(execute(&mut database, "UPDATE people WHERE { id = 'a' } SET { age: '31' }")),
+            SchemaViolation::InvalidType(_, "integer", "string")
+        ));
+        assert!(matches!(
+            violation(execute(&mut database, "UPDATE people WHERE { id = 'a' } SET { age: null }")),
+            SchemaViolation::NullAttribute(_)
+        ));
+        assert!(matches!(
+            violation(execute(&mut database, "PUT metrics { id: 'm', ts: 2, value: '1' }")),
+            SchemaViolation::InvalidType(_, "float", "string")
+        ));
+
+        let item = get_item(&mut database, "GET people WHERE { id = 'a' }");
+        assert_eq!(item.get("age"), Some(&Value::Integer(31)));
+
+        // A float attribute accepts an integer and stores it as a float.
+        execute(&mut database, "PUT metrics { id: 'm', ts: 2, value: 3 }").unwrap();
+        execute(&mut database, "UPDATE metrics WHERE { id = 'm', ts = 1 } SET { value: 2 }").unwrap();
+        let QueryOutput::Items(items) = execute(&mut database, "QUERY metrics WHERE { id = 'm' }").unwrap()
+        else {
+            panic!("expected items");
+        };
+        let values: Vec<_> = items.iter().map(|item| item.get("value")).collect();
+        assert_eq!(values, [Some(&Value::Float(2.0)), Some(&Value::Float(3.0))]);
+
+        assert!(matches!(
+            execute(
+                &mut database,
+                "CREATE STORE broken HASH KEY id STRING WITH SCHEMA { id: STRING }"
+            ),
+            Err(PliteDbError::EngineError(EngineError::SchemaKeyAttribute(..)))
+        ));
+        assert!(matches!(
+            execute(
+                &mut database,
+                "CREATE STORE broken HASH KEY id STRING WITH SCHEMA { a: LIST, a: MAP }"
+            ),
+            Err(PliteDbError::EngineError(EngineError::DuplicateSchemaAttribute(..)))
+        ));
+    }
+}
@@ -0,0 +1,87 @@
+// Helpers shared by the engine's tests, which run statements against a database holding a simple
+// `users` store and an `events` store partitioned by device.
+use std::collections::HashMap;
+
+use crate::{
+    error::PliteDbResult,
+    query::{
+        lexer::tokenize,
+        parser::{parse, parse_script}
+    }
+};
+
+use super::{
+    executor::QueryOutput,
+    model::{Database, Value}
+};
+
+pub fn execute(
+    database: &mut Database,
+    input: &str
+) -> PliteDbResult<QueryOutput> {
+    return database.execute(parse(tokenize(input)?)?);
+}
+
+pub fn run_script(
+    database: &mut Database,
+    script: &str,
+    atomic: bool
+) -> PliteDbResult<Vec<QueryOutput>> {
+    return database.execute_script(parse_script(tokenize(script)?)?, atomic);
+}
+
+pub fn test_database() -> Database {
+    let mut database = Database::new(String::from("test"));
+
+    execute(&mut database, "CREATE STORE users HASH KEY userId STRING").unwrap();
+    execute(
+        &mut database,
+        "CREATE STORE events HASH KEY deviceId STRING SORT KEY ts INTEGER"
+    )
+    .unwrap();
+
+    return database;
+}
+
+pub fn get_item(
+    database: &mut Database,
+    input: &str
+) -> HashMap<String, Value> {
+    let QueryOutput::Item(Some(item)) = execute(database, input).unwrap()
+    else {
+        panic!("expected an item");
+    };
+
+    return item;
+}
+
+pub fn scan_page(
+    database: &mut Database,
+    input: &str,
+    key_name: &str
+) -> (Vec<Value>, Option<HashMap<String, Value>>) {
+    let QueryOutput::Page { items, last_evaluated_key } = execute(database, input).unwrap()
+    else {
+        panic!("expected a page");
+    };
+
+    return (
+        items.into_iter().map(|item| item.get(key_name).cloned().unwrap()).collect(),
+        last_evaluated_key
+    );
+}
+
+pub fn user_ids(database: &mut Database) -> Vec<Value> {
+    let (items, _) = scan_page(database, "SCAN users", "userId");
+
+    return items;
+}
+
+pub fn sort_keys(output: QueryOutput) -> Vec<Value> {
+    let QueryOutput::Items(items) = output
+    else {
+        panic!("expected items");
+    };
+
+    return items.into_iter().map(|item| item.get("ts").cloned().unwrap()).collect();
+}
@@ -0,0 +1,130 @@
+use super::{
+    error::{EngineError, EngineResult},
+    model::Database
+};
+
+// A transaction snapshots every store when it begins, so rolling back restores the database exactly,
+// including stores created or dropped since. Transactions do not nest.
+impl Database {
+    pub fn begin(&mut self) -> EngineResult<()> {
+        if self.snapshot.is_some() {
+            return Err(EngineError::TransactionAlreadyOpen);
+        }
+
+        self.snapshot = Some(self.store_by_name.clone());
+
+        return Ok(());
+    }
+
+    pub fn commit(&mut self) -> EngineResult<()> {
+        return match self.snapshot.take() {
+            Some(_) => Ok(()),
+            None => Err(EngineError::NoOpenTransaction)
+        };
+    }
+
+    pub fn rollback(&mut self) -> EngineResult<()> {
+        let snapshot = self.snapshot.take().ok_or(EngineError::NoOpenTransaction)?;
+
+        self.store_by_name = snapshot;
+
+        return Ok(());
+    }
+
+    pub fn in_transaction(&self) -> bool {
+        return self.snapshot.is_some();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        engine::{
+            error::EngineError,
+            executor::QueryOutput,
+            model::Value,
+            testing::{execute, run_script, test_database, user_ids}
+        },
+        error::PliteDbError,
+        query::parser::error::ParserError
+    };
+
+    #[test]
+    fn runs_scripts_and_transactions() {
+        let mut database = test_database();
+
+        let outputs = run_script(
+            &mut database,
+            "-- seed data\nPUT users { userId: 'a' };; PUT users { userId: 'b' };\nGET users WHERE { userId = 'a' };",
+            true
+        )
+        .unwrap();
+        assert_eq!(outputs.len(), 3);
+        assert_eq!(outputs[0], QueryOutput::Written);
+        assert_eq!(run_script(&mut database, " ; -- nothing to run", true).unwrap(), []);
+
+        // The failing update rolls back the whole atomic script, but not a plain one.
+        let Err(PliteDbError::ScriptError(index, outputs, error)) = run_script(
+            &mut database,
+            "PUT users { userId: 'c' }; UPDATE users WHERE { userId = 'z' } SET { age: 1 }",
+            true
+        )
+        else {
+            panic!("expected a script error");
+        };
+        assert_eq!((index, outputs), (1, Vec::new()));
+        assert!(matches!(*error, PliteDbError::EngineError(EngineError::ItemNotFound(_))));
+        assert_eq!(user_ids(&mut database), ["a", "b"].map(|id| Value::String(id.to_string())));
+        assert!(!database.in_transaction());
+
+        // A plain script keeps the outputs of the statements applied before the failure.
+        let Err(PliteDbError::ScriptError(index, outputs, _)) = run_script(
+            &mut database,
+            "PUT users { userId: 'c' }; UPDATE users WHERE { userId = 'z' } SET { age: 1 }; PUT users { userId: 'd' }",
+            false
+        )
+        else {
+            panic!("expected a script error");
+        };
+        assert_eq!((index, outputs), (1, vec![QueryOutput::Written]));
+        assert_eq!(user_ids(&mut database), ["a", "b", "c"].map(|id| Value::String(id.to_string())));
+
+        assert!(matches!(
+            run_script(&mut database, "BEGIN; PUT users { userId: 'd' }; COMMIT", true),
+            Err(PliteDbError::EngineError(EngineError::TransactionControlInScript))
+        ));
+
+        let outputs = run_script(
+            &mut database,
+            "begin transaction; DELETE users WHERE { userId = 'a' }; DROP STORE events; ROLLBACK",
+            false
+        )
+        .unwrap();
+        assert_eq!(outputs[0], QueryOutput::TransactionStarted);
+        assert_eq!(outputs[3], QueryOutput::RolledBack);
+        assert_eq!(user_ids(&mut database), ["a", "b", "c"].map(|id| Value::String(id.to_string())));
+        assert!(execute(&mut database, "SCAN events").is_ok());
+
+        execute(&mut database, "BEGIN").unwrap();
+        execute(&mut database, "DELETE users WHERE { userId = 'c' }").unwrap();
+        assert!(matches!(
+            execute(&mut database, "BEGIN"),
+            Err(PliteDbError::EngineError(EngineError::TransactionAlreadyOpen))
+        ));
+        assert_eq!(execute(&mut database, "COMMIT TRANSACTION;").unwrap(), QueryOutput::Committed);
+        assert_eq!(user_ids(&mut database), ["a", "b"].map(|id| Value::String(id.to_string())));
+        assert!(matches!(
+            execute(&mut database, "ROLLBACK"),
+            Err(PliteDbError::EngineError(EngineError::NoOpenTransaction))
+        ));
+
+        assert!(matches!(
+            execute(&mut database, "GET users WHERE { userId = 'a' } LIMIT 1"),
+            Err(PliteDbError::ParserError(ParserError::UnexpectedToken(..)))
+        ));
+        assert!(matches!(
+            run_script(&mut database, "PUT users { userId: 'e' } PUT users { userId: 'f' }", false),
+            Err(PliteDbError::ParserError(ParserError::UnexpectedToken(..)))
+        ));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::query::{error::QueryError, lexer::error::LexerError, parser::error::ParserError};
+use crate::{
+    engine::{error::EngineError, evaluator::error::EvaluationError, executor::QueryOutput},
+    query::{error::QueryError, lexer::error::LexerError, parser::error::ParserError}
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum PliteDbError {
@@ -9,7 +12,14 @@ pub enum PliteDbError {
     #[error("Query error: {0}")]
     QueryError(#[from] QueryError),
     #[error("Parser error: {0}")]
-    ParserError(#[from] ParserError)
+    ParserError(#[from] ParserError),
+    #[error("Evaluation error: {0}")]
+    EvaluationError(#[from] EvaluationError),
+    #[error("Engine error: {0}")]
+    EngineError(#[from] EngineError),
+    // The index of the failing statement, the outputs of the statements applied before it and its error.
+    #[error("Statement {number} of the script failed: {2}", number = .0 + 1)]
+    ScriptError(usize, Vec<QueryOutput>, Box<PliteDbError>)
 }
 
 pub type PliteDbResult<T> = Result<T, PliteDbError>;
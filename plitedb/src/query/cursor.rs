@@ -65,6 +65,15 @@ where
         return self.location;
     }
 
+    pub fn peek_loc(&mut self) -> Location {
+        let location = self.location;
+
+        return match self.items.peek() {
+            Some(next) => next.next_location(location),
+            None => location
+        };
+    }
+
     pub fn peek(&mut self) -> Option<&I::Item> {
         return self.items.peek();
     }
@@ -1,7 +1,25 @@
+use crate::query::cursor::Location;
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum LexerError {
     #[error("Unexpected character '{0}'")]
     UnexpectedCharacter(char),
+    #[error("Invalid {0}'{1}' literal")]
+    InvalidLiteral(String, String),
+    #[error("Invalid number '{0}' at {1}")]
+    InvalidNumber(String, Location),
+    #[error("Number '{0}' at {1} is out of range")]
+    NumberOutOfRange(String, Location),
+    #[error("Invalid escape sequence '\\{0}' at {1}")]
+    InvalidEscape(char, Location),
+    #[error("Invalid unicode escape at {0}. Expected \\u{{...}} with 1 to 6 hex digits naming a character")]
+    InvalidUnicodeEscape(Location),
+    #[error("Unterminated escape sequence at {0}")]
+    UnterminatedEscape(Location),
+    #[error("Empty quoted identifier at {0}")]
+    EmptyIdentifier(Location),
+    #[error("Unterminated block comment starting at {0}")]
+    UnterminatedComment(Location),
     #[error("Unexpected end of input")]
     UnexpectedEndOfInput
 }
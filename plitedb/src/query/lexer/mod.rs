@@ -1,6 +1,10 @@
 pub mod error;
 pub mod token;
 
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, TimeDelta};
+
 use super::cursor::{CursorTrackable, Location, PeekingCursor};
 
 use error::{LexerError, LexerResult};
@@ -22,56 +26,48 @@ pub fn tokenize(haystack: &str) -> LexerResult<Vec<Token>> {
     let mut tokens = Vec::with_capacity(32);
 
     let mut chars = PeekingCursor::new(haystack.chars());
-    while let Some(char) = chars.peek() {
+    while let Some(&char) = chars.peek() {
         if char.is_whitespace() {
             chars.next();
             continue;
         };
 
-        if char.is_numeric() {
-            tokens.push(Token::new(
-                TokenType::Number(String::from_iter(
-                    chars.peek_and_take_while(|next| next.is_numeric() || *next == '.')
-                )),
-                chars.loc()
-            ));
+        let location = chars.peek_loc();
+
+        if char.is_ascii_digit() {
+            tokens.push(Token::new(lex_number(&mut chars, String::new(), location)?, location));
         }
-        else if char.is_alphabetic() || *char == '_' {
+        else if char.is_alphabetic() || char == '_' {
             let string = String::from_iter(chars.peek_and_take_while(|next| next.is_alphanumeric() || *next == '_'));
-            let token_type = match string.len() {
-                3 => match &string[..] {
-                    "GET" => TokenType::Keyword(Keyword::Get),
-                    "PUT" => TokenType::Keyword(Keyword::Put),
-                    _ => TokenType::Identifier(string)
-                },
-                4 => match &string[..] {
-                    "true" => TokenType::Keyword(Keyword::True),
-                    "false" => TokenType::Keyword(Keyword::False),
-                    _ => TokenType::Identifier(string)
-                },
-                5 => match &string[..] {
-                    "WHERE" => TokenType::Keyword(Keyword::Where),
-                    _ => TokenType::Identifier(string)
-                },
+
+            if chars.peek() == Some(&'\'') && matches!(&string[..], "x" | "b64" | "ts" | "dur") {
+                let literal = lex_quoted(&mut chars)?;
+
+                let token_type = lex_prefixed_literal(&string, &literal).ok_or(LexerError::InvalidLiteral(string, literal))?;
+                tokens.push(Token::new(token_type, location));
+
+                continue;
+            }
+
+            let token_type = match Keyword::from_word(&string) {
+                Some(keyword) if keyword.is_reserved() => TokenType::Keyword(keyword),
                 _ => TokenType::Identifier(string)
             };
 
-            tokens.push(Token::new(token_type, chars.loc()));
+            tokens.push(Token::new(token_type, location));
         }
-        else if *char == '\'' || *char == '"' {
-            let quote = chars.next().unwrap();
-            let string = String::from_iter(chars.peek_and_take_while(|next| *next != quote));
+        else if char == '\'' || char == '"' {
+            tokens.push(Token::new(TokenType::String(lex_quoted(&mut chars)?), location));
+        }
+        // Backticks quote an identifier, which may then be a keyword or contain any character.
+        else if char == '`' {
+            let name = lex_quoted(&mut chars)?;
 
-            if let Some(closing_quote) = chars.next() {
-                if closing_quote != quote {
-                    return Err(LexerError::UnexpectedCharacter(closing_quote));
-                }
-            }
-            else {
-                return Err(LexerError::UnexpectedEndOfInput);
+            if name.is_empty() {
+                return Err(LexerError::EmptyIdentifier(location));
             }
 
-            tokens.push(Token::new(TokenType::String(string), chars.loc()));
+            tokens.push(Token::new(TokenType::QuotedIdentifier(name), location));
         }
         else {
             let next = chars.next().unwrap();
@@ -81,15 +77,24 @@ pub fn tokenize(haystack: &str) -> LexerResult<Vec<Token>> {
                 ')' => TokenType::RightParenthesis,
                 '{' => TokenType::LeftCurlyBrace,
                 '}' => TokenType::RightCurlyBrace,
+                '[' => TokenType::LeftSquareBracket,
+                ']' => TokenType::RightSquareBracket,
                 ':' => TokenType::Colon,
                 ';' => TokenType::Semicolon,
                 ',' => TokenType::Comma,
-                '.' => TokenType::Period,
+                '.' => match chars.peek() {
+                    Some(next) if next.is_ascii_digit() => lex_number(&mut chars, String::from("."), location)?,
+                    _ => TokenType::Period
+                },
                 '>' => match chars.peek() {
                     Some('=') => {
                         chars.next();
                         TokenType::GreaterThanOrEqual
                     },
+                    Some('>') => {
+                        chars.next();
+                        TokenType::RightDoubleAngleBracket
+                    },
                     _ => TokenType::GreaterThan
                 },
                 '<' => match chars.peek() {
@@ -97,6 +102,10 @@ pub fn tokenize(haystack: &str) -> LexerResult<Vec<Token>> {
                         chars.next();
                         TokenType::LessThanOrEqual
                     },
+                    Some('<') => {
+                        chars.next();
+                        TokenType::LeftDoubleAngleBracket
+                    },
                     _ => TokenType::LessThan
                 },
                 '=' => TokenType::Equal,
@@ -109,22 +118,327 @@ pub fn tokenize(haystack: &str) -> LexerResult<Vec<Token>> {
                 },
                 '*' => TokenType::Asterisk,
                 '+' => TokenType::Plus,
-                '-' => TokenType::Minus,
-                '/' => TokenType::Slash,
+                '-' => match chars.peek() {
+                    Some('-') => {
+                        skip_line_comment(&mut chars);
+                        continue;
+                    },
+                    _ => TokenType::Minus
+                },
+                '/' => match chars.peek() {
+                    Some('*') => {
+                        skip_block_comment(&mut chars, location)?;
+                        continue;
+                    },
+                    _ => TokenType::Slash
+                },
                 '%' => TokenType::Percent,
                 '^' => TokenType::Caret,
                 _ => return Err(LexerError::UnexpectedCharacter(next))
             };
 
-            tokens.push(Token::new(token_type, chars.loc()));
+            tokens.push(Token::new(token_type, location));
         }
     }
 
     return Ok(tokens);
 }
 
+fn skip_line_comment<I: Iterator<Item = char>>(chars: &mut PeekingCursor<I>) {
+    chars.peek_and_take_while(|next| *next != '\n');
+}
+
+// Called after the opening `/`. Block comments nest, so commenting out a block that already contains
+// one works as expected.
+fn skip_block_comment<I: Iterator<Item = char>>(
+    chars: &mut PeekingCursor<I>,
+    location: Location
+) -> LexerResult<()> {
+    chars.next();
+
+    let mut depth = 1;
+
+    while depth > 0 {
+        match chars.next() {
+            Some('/') if chars.peek() == Some(&'*') => {
+                chars.next();
+                depth += 1;
+            },
+            Some('*') if chars.peek() == Some(&'/') => {
+                chars.next();
+                depth -= 1;
+            },
+            Some(_) => (),
+            None => return Err(LexerError::UnterminatedComment(location))
+        }
+    }
+
+    return Ok(());
+}
+
+// Takes every character that could belong to the number, so that malformed numbers such as `1.2.3` or
+// `12ab` are rejected as a whole rather than split into several tokens.
+fn lex_number<I: Iterator<Item = char>>(
+    chars: &mut PeekingCursor<I>,
+    mut text: String,
+    location: Location
+) -> LexerResult<TokenType> {
+    while let Some(&next) = chars.peek() {
+        let exponent_sign = matches!(next, '+' | '-') && text.ends_with(['e', 'E']) && radix(&text).is_none();
+
+        if !(next.is_ascii_alphanumeric() || next == '_' || next == '.' || exponent_sign) {
+            break;
+        }
+
+        text.push(next);
+        chars.next();
+    }
+
+    return parse_number(&text, location);
+}
+
+// Integers are written in decimal, or in hex or binary with a `0x` or `0b` prefix. A fraction or an
+// exponent makes a float, and a `d` suffix makes an exact decimal, such as `19.99d`. Underscores may
+// separate digits.
+fn parse_number(
+    text: &str,
+    location: Location
+) -> LexerResult<TokenType> {
+    let invalid = || LexerError::InvalidNumber(text.to_string(), location);
+    let out_of_range = || LexerError::NumberOutOfRange(text.to_string(), location);
+
+    if let Some((digits, radix)) = radix(text) {
+        let digits = strip_separators(digits, radix).ok_or_else(invalid)?;
+
+        return u64::from_str_radix(&digits, radix).map(TokenType::Integer).map_err(|_| out_of_range());
+    }
+
+    let (number, decimal) = match text.strip_suffix('d') {
+        Some(number) => (number, true),
+        None => (text, false)
+    };
+
+    let (mantissa, exponent) = match number.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (number, None)
+    };
+
+    let (whole, fraction) = match mantissa.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (mantissa, None)
+    };
+
+    let mut normalized = match (whole, fraction) {
+        ("", Some(_)) => String::from("0"),
+        (whole, _) => strip_separators(whole, 10).ok_or_else(invalid)?
+    };
+
+    if let Some(fraction) = fraction {
+        normalized.push('.');
+        normalized.push_str(&strip_separators(fraction, 10).ok_or_else(invalid)?);
+    }
+
+    if let Some(exponent) = exponent {
+        let (sign, digits) = match exponent.strip_prefix(['+', '-']) {
+            Some(digits) => (&exponent[..1], digits),
+            None => ("", exponent)
+        };
+
+        normalized.push('e');
+        normalized.push_str(sign);
+        normalized.push_str(&strip_separators(digits, 10).ok_or_else(invalid)?);
+    }
+
+    if decimal {
+        return normalized.parse::<BigDecimal>().map(TokenType::Decimal).map_err(|_| out_of_range());
+    }
+
+    if fraction.is_none() && exponent.is_none() {
+        return normalized.parse::<u64>().map(TokenType::Integer).map_err(|_| out_of_range());
+    }
+
+    return match normalized.parse::<f64>() {
+        Ok(float) if float.is_finite() => Ok(TokenType::Float(float)),
+        _ => Err(out_of_range())
+    };
+}
+
+fn radix(text: &str) -> Option<(&str, u32)> {
+    if let Some(digits) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        return Some((digits, 16));
+    }
+
+    if let Some(digits) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        return Some((digits, 2));
+    }
+
+    return None;
+}
+
+// Removes the underscores between digits, rejecting empty digit sequences and misplaced underscores.
+fn strip_separators(
+    digits: &str,
+    radix: u32
+) -> Option<String> {
+    let mut stripped = String::with_capacity(digits.len());
+    let mut previous = None;
+
+    for char in digits.chars() {
+        match char {
+            '_' if previous.is_some_and(|previous: char| previous != '_') => (),
+            char if char.is_digit(radix) => stripped.push(char),
+            _ => return None
+        }
+
+        previous = Some(char);
+    }
+
+    if stripped.is_empty() || previous == Some('_') {
+        return None;
+    }
+
+    return Some(stripped);
+}
+
+// Quoted text supports backslash escapes and, as in SQL, a doubled quote stands for the quote itself.
+fn lex_quoted<I: Iterator<Item = char>>(chars: &mut PeekingCursor<I>) -> LexerResult<String> {
+    let quote = chars.next().ok_or(LexerError::UnexpectedEndOfInput)?;
+    let mut string = String::new();
+
+    loop {
+        match chars.next() {
+            Some('\\') => string.push(lex_escape(chars)?),
+            Some(char) if char == quote => {
+                if chars.peek() != Some(&quote) {
+                    break;
+                }
+
+                chars.next();
+                string.push(quote);
+            },
+            Some(char) => string.push(char),
+            None => return Err(LexerError::UnexpectedEndOfInput)
+        }
+    }
+
+    return Ok(string);
+}
+
+// Called after the backslash, whose location is reported on errors.
+fn lex_escape<I: Iterator<Item = char>>(chars: &mut PeekingCursor<I>) -> LexerResult<char> {
+    let location = chars.loc();
+
+    let escaped = match chars.next() {
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some('t') => '\t',
+        Some('0') => '\0',
+        Some('\\') => '\\',
+        Some('\'') => '\'',
+        Some('"') => '"',
+        Some('u') => return lex_unicode_escape(chars, location),
+        Some(char) => return Err(LexerError::InvalidEscape(char, location)),
+        None => return Err(LexerError::UnterminatedEscape(location))
+    };
+
+    return Ok(escaped);
+}
+
+fn lex_unicode_escape<I: Iterator<Item = char>>(
+    chars: &mut PeekingCursor<I>,
+    location: Location
+) -> LexerResult<char> {
+    match chars.next() {
+        Some('{') => (),
+        Some(_) => return Err(LexerError::InvalidUnicodeEscape(location)),
+        None => return Err(LexerError::UnterminatedEscape(location))
+    }
+
+    let digits = String::from_iter(chars.peek_and_take_while(|next| next.is_ascii_hexdigit()));
+
+    match chars.next() {
+        Some('}') => (),
+        Some(_) => return Err(LexerError::InvalidUnicodeEscape(location)),
+        None => return Err(LexerError::UnterminatedEscape(location))
+    }
+
+    if digits.is_empty() || digits.len() > 6 {
+        return Err(LexerError::InvalidUnicodeEscape(location));
+    }
+
+    return u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or(LexerError::InvalidUnicodeEscape(location));
+}
+
+fn lex_prefixed_literal(
+    prefix: &str,
+    literal: &str
+) -> Option<TokenType> {
+    return match prefix {
+        "x" => decode_hex(literal).map(TokenType::Bytes),
+        "b64" => BASE64.decode(literal).ok().map(TokenType::Bytes),
+        "ts" => DateTime::parse_from_rfc3339(literal)
+            .ok()
+            .map(|timestamp| TokenType::Timestamp(timestamp.to_utc())),
+        "dur" => parse_duration(literal).map(TokenType::Duration),
+        _ => None
+    };
+}
+
+// Durations are written as a sequence of amounts and units, such as `1d12h` or `-250ms`. The units
+// are d, h, m, s, ms and us.
+fn parse_duration(literal: &str) -> Option<TimeDelta> {
+    let (negative, mut rest) = match literal.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, literal)
+    };
+
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut duration = TimeDelta::zero();
+
+    while !rest.is_empty() {
+        let amount_length = rest.find(|char: char| !char.is_ascii_digit()).unwrap_or(rest.len());
+        let amount = rest[..amount_length].parse::<i64>().ok()?;
+        rest = &rest[amount_length..];
+
+        let unit_length = rest.find(|char: char| char.is_ascii_digit()).unwrap_or(rest.len());
+        let unit = match &rest[..unit_length] {
+            "d" => TimeDelta::try_days(amount)?,
+            "h" => TimeDelta::try_hours(amount)?,
+            "m" => TimeDelta::try_minutes(amount)?,
+            "s" => TimeDelta::try_seconds(amount)?,
+            "ms" => TimeDelta::try_milliseconds(amount)?,
+            "us" => TimeDelta::microseconds(amount),
+            _ => return None
+        };
+        rest = &rest[unit_length..];
+
+        duration = duration.checked_add(&unit)?;
+    }
+
+    return Some(if negative { -duration } else { duration });
+}
+
+fn decode_hex(literal: &str) -> Option<Vec<u8>> {
+    if !literal.len().is_multiple_of(2) || !literal.is_ascii() {
+        return None;
+    }
+
+    return (0..literal.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&literal[index..index + 2], 16).ok())
+        .collect();
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono::{TimeZone, Utc};
+
     use crate::query::cursor::Location;
 
     use super::*;
@@ -133,34 +447,221 @@ mod tests {
     fn tokenize_put_statement() {
         let input = "PUT users { userId: 'abcd123', name: 'Alice', age: 30, isRegistered: true }";
 
-        let expected_types = vec![
-            TokenType::Keyword(Keyword::Put),
-            TokenType::Identifier(String::from("users")),
-            TokenType::LeftCurlyBrace,
-            TokenType::Identifier(String::from("userId")),
-            TokenType::Colon,
-            TokenType::String(String::from("abcd123")),
-            TokenType::Comma,
-            TokenType::Identifier(String::from("name")),
-            TokenType::Colon,
-            TokenType::String(String::from("Alice")),
-            TokenType::Comma,
-            TokenType::Identifier(String::from("age")),
-            TokenType::Colon,
-            TokenType::Number(String::from("30")),
-            TokenType::Comma,
-            TokenType::Identifier(String::from("isRegistered")),
-            TokenType::Colon,
-            TokenType::Keyword(Keyword::True),
-            TokenType::RightCurlyBrace,
+        let expected_types = [
+            (TokenType::Keyword(Keyword::Put), 1),
+            (TokenType::Identifier(String::from("users")), 5),
+            (TokenType::LeftCurlyBrace, 11),
+            (TokenType::Identifier(String::from("userId")), 13),
+            (TokenType::Colon, 19),
+            (TokenType::String(String::from("abcd123")), 21),
+            (TokenType::Comma, 30),
+            (TokenType::Identifier(String::from("name")), 32),
+            (TokenType::Colon, 36),
+            (TokenType::String(String::from("Alice")), 38),
+            (TokenType::Comma, 45),
+            (TokenType::Identifier(String::from("age")), 47),
+            (TokenType::Colon, 50),
+            (TokenType::Integer(30), 52),
+            (TokenType::Comma, 54),
+            (TokenType::Identifier(String::from("isRegistered")), 56),
+            (TokenType::Colon, 68),
+            (TokenType::Keyword(Keyword::True), 70),
+            (TokenType::RightCurlyBrace, 75)
         ];
 
         let expected: Vec<Token> = expected_types
-            .iter()
-            .zip(1..)
-            .map(|(token_type, column)| Token::new(token_type.clone(), Location { line: 1, column }))
+            .into_iter()
+            .map(|(token_type, column)| Token::new(token_type, Location { line: 1, column }))
             .collect();
 
         assert_eq!(tokenize(input).unwrap(), expected);
     }
+
+    #[test]
+    fn tokenize_keywords_regardless_of_case() {
+        let tokens = tokenize("get Get GET True nULL begin `select` `a b``c` getter Where").unwrap();
+        let token_types: Vec<TokenType> = tokens.into_iter().map(|token| token.token_type).collect();
+
+        assert_eq!(
+            token_types,
+            [
+                TokenType::Keyword(Keyword::Get),
+                TokenType::Keyword(Keyword::Get),
+                TokenType::Keyword(Keyword::Get),
+                TokenType::Keyword(Keyword::True),
+                TokenType::Keyword(Keyword::Null),
+                TokenType::Keyword(Keyword::Begin),
+                TokenType::QuotedIdentifier(String::from("select")),
+                TokenType::QuotedIdentifier(String::from("a b`c")),
+                TokenType::Identifier(String::from("getter")),
+                TokenType::Identifier(String::from("Where"))
+            ]
+        );
+
+        assert!(matches!(
+            tokenize("PUT users { `` : 1 }"),
+            Err(LexerError::EmptyIdentifier(Location { line: 1, column: 13 }))
+        ));
+        assert!(matches!(tokenize("`unterminated"), Err(LexerError::UnexpectedEndOfInput)));
+    }
+
+    #[test]
+    fn tokenize_comments() {
+        let input = "-- find adults\nSCAN users /* all of them /* nested */ */ FILTER { age >= 18 } -- inline\n/**/LIMIT 10 -- trailing";
+
+        let expected_types = [
+            (TokenType::Keyword(Keyword::Scan), 2, 1),
+            (TokenType::Identifier(String::from("users")), 2, 6),
+            (TokenType::Identifier(String::from("FILTER")), 2, 43),
+            (TokenType::LeftCurlyBrace, 2, 50),
+            (TokenType::Identifier(String::from("age")), 2, 52),
+            (TokenType::GreaterThanOrEqual, 2, 56),
+            (TokenType::Integer(18), 2, 59),
+            (TokenType::RightCurlyBrace, 2, 62),
+            (TokenType::Identifier(String::from("LIMIT")), 3, 5),
+            (TokenType::Integer(10), 3, 11)
+        ];
+
+        let expected: Vec<Token> = expected_types
+            .into_iter()
+            .map(|(token_type, line, column)| Token::new(token_type, Location { line, column }))
+            .collect();
+
+        assert_eq!(tokenize(input).unwrap(), expected);
+        assert_eq!(
+            tokenize("a - b / c").unwrap().into_iter().map(|token| token.token_type).collect::<Vec<_>>(),
+            [
+                TokenType::Identifier(String::from("a")),
+                TokenType::Minus,
+                TokenType::Identifier(String::from("b")),
+                TokenType::Slash,
+                TokenType::Identifier(String::from("c"))
+            ]
+        );
+        assert!(matches!(
+            tokenize("SCAN users\n  /* unterminated /* nested */"),
+            Err(LexerError::UnterminatedComment(Location { line: 2, column: 3 }))
+        ));
+    }
+
+    #[test]
+    fn tokenize_numbers() {
+        let tokens = tokenize("42 1_000_000 0xFF 0b1010 1e9 2.5E-3 .5 3.25 1_0.5e+1_0 19.99d 0xFFd a.b").unwrap();
+        let token_types: Vec<TokenType> = tokens.into_iter().map(|token| token.token_type).collect();
+
+        assert_eq!(
+            token_types,
+            [
+                TokenType::Integer(42),
+                TokenType::Integer(1_000_000),
+                TokenType::Integer(255),
+                TokenType::Integer(10),
+                TokenType::Float(1e9),
+                TokenType::Float(2.5e-3),
+                TokenType::Float(0.5),
+                TokenType::Float(3.25),
+                TokenType::Float(10.5e10),
+                TokenType::Decimal("19.99".parse().unwrap()),
+                TokenType::Integer(0xFFD),
+                TokenType::Identifier(String::from("a")),
+                TokenType::Period,
+                TokenType::Identifier(String::from("b"))
+            ]
+        );
+
+        for input in ["1.2.3", "12ab", "1__0", "1_", "0x", "0b102", "1e", "1.", "1.e5"] {
+            assert!(
+                matches!(tokenize(input), Err(LexerError::InvalidNumber(number, _)) if number == input),
+                "{}",
+                input
+            );
+        }
+
+        assert!(matches!(
+            tokenize("age > 1.2.3"),
+            Err(LexerError::InvalidNumber(_, Location { line: 1, column: 7 }))
+        ));
+        assert_eq!(
+            tokenize("9223372036854775808").unwrap()[0].token_type,
+            TokenType::Integer(9223372036854775808)
+        );
+        assert!(matches!(
+            tokenize("18446744073709551616"),
+            Err(LexerError::NumberOutOfRange(..))
+        ));
+        assert!(matches!(tokenize("1e999"), Err(LexerError::NumberOutOfRange(..))));
+        assert!(matches!(tokenize("\u{b2}"), Err(LexerError::UnexpectedCharacter('\u{b2}'))));
+    }
+
+    #[test]
+    fn tokenize_string_escapes() {
+        let tokens = tokenize(r#"'O\'Brien' 'O''Brien' "say \"hi\"\n\tbye" 'a\\b' '\u{1F600}\u{e9}' """""#).unwrap();
+        let token_types: Vec<TokenType> = tokens.into_iter().map(|token| token.token_type).collect();
+
+        assert_eq!(
+            token_types,
+            [
+                TokenType::String(String::from("O'Brien")),
+                TokenType::String(String::from("O'Brien")),
+                TokenType::String(String::from("say \"hi\"\n\tbye")),
+                TokenType::String(String::from("a\\b")),
+                TokenType::String(String::from("\u{1F600}\u{e9}")),
+                TokenType::String(String::from("\""))
+            ]
+        );
+
+        assert!(matches!(
+            tokenize(r"name: 'a\qb'"),
+            Err(LexerError::InvalidEscape('q', Location { line: 1, column: 9 }))
+        ));
+        assert!(matches!(tokenize(r"'\u{110000}'"), Err(LexerError::InvalidUnicodeEscape(_))));
+        assert!(matches!(tokenize(r"'\u{}'"), Err(LexerError::InvalidUnicodeEscape(_))));
+        assert!(matches!(tokenize(r"'\u0041'"), Err(LexerError::InvalidUnicodeEscape(_))));
+        assert!(matches!(tokenize(r"'\u{41"), Err(LexerError::UnterminatedEscape(_))));
+        assert!(matches!(tokenize(r"'abc\"), Err(LexerError::UnterminatedEscape(_))));
+        assert!(matches!(tokenize(r"'abc\'"), Err(LexerError::UnexpectedEndOfInput)));
+    }
+
+    #[test]
+    fn tokenize_bytes_literals() {
+        let tokens = tokenize("x'00ff1A' b64'3q2+7w==' x''").unwrap();
+        let token_types: Vec<TokenType> = tokens.into_iter().map(|token| token.token_type).collect();
+
+        assert_eq!(
+            token_types,
+            [
+                TokenType::Bytes(vec![0x00, 0xff, 0x1a]),
+                TokenType::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+                TokenType::Bytes(vec![])
+            ]
+        );
+
+        assert!(matches!(tokenize("x'abc'"), Err(LexerError::InvalidLiteral(..))));
+        assert!(matches!(tokenize("x'zz'"), Err(LexerError::InvalidLiteral(..))));
+        assert!(matches!(tokenize("b64'@@'"), Err(LexerError::InvalidLiteral(..))));
+        assert!(matches!(tokenize("x'00"), Err(LexerError::UnexpectedEndOfInput)));
+    }
+
+    #[test]
+    fn tokenize_temporal_literals() {
+        let tokens = tokenize("ts'2024-03-01T12:30:00.000001+02:00' dur'1d12h' dur'-250ms'").unwrap();
+        let token_types: Vec<TokenType> = tokens.into_iter().map(|token| token.token_type).collect();
+
+        assert_eq!(
+            token_types,
+            [
+                TokenType::Timestamp(Utc.with_ymd_and_hms(2024, 3, 1, 10, 30, 0).unwrap() + TimeDelta::microseconds(1)),
+                TokenType::Duration(TimeDelta::hours(36)),
+                TokenType::Duration(TimeDelta::milliseconds(-250))
+            ]
+        );
+
+        assert!(matches!(
+            tokenize("ts'2024-13-01T00:00:00Z'"),
+            Err(LexerError::InvalidLiteral(..))
+        ));
+        assert!(matches!(tokenize("ts'2024-03-01'"), Err(LexerError::InvalidLiteral(..))));
+        assert!(matches!(tokenize("dur'12'"), Err(LexerError::InvalidLiteral(..))));
+        assert!(matches!(tokenize("dur'1w'"), Err(LexerError::InvalidLiteral(..))));
+    }
 }
@@ -1,11 +1,18 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, TimeDelta, Utc};
+
 use crate::query::cursor::Location;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     LeftParenthesis,
     RightParenthesis,
     LeftCurlyBrace,
     RightCurlyBrace,
+    LeftSquareBracket,
+    RightSquareBracket,
+    LeftDoubleAngleBracket,
+    RightDoubleAngleBracket,
 
     Colon,
     Semicolon,
@@ -29,12 +36,20 @@ pub enum TokenType {
 
     Keyword(Keyword),
 
-    Number(String),
+    // The magnitude only, as a leading minus is applied by the parser.
+    Integer(u64),
+    Float(f64),
+    Decimal(BigDecimal),
     String(String),
-    Identifier(String)
+    Bytes(Vec<u8>),
+    Timestamp(DateTime<Utc>),
+    Duration(TimeDelta),
+    Identifier(String),
+    // A backtick-quoted identifier, which is always a name and never a keyword.
+    QuotedIdentifier(String)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Token {
     pub token_type: TokenType,
     pub location: Location
@@ -49,11 +64,142 @@ impl Token {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Keyword {
     Get,
     Put,
     Where,
     True,
-    False
+    False,
+    Create,
+    Drop,
+    Store,
+    Hash,
+    Sort,
+    Key,
+    Query,
+    Between,
+    And,
+    Or,
+    BeginsWith,
+    Update,
+    Upsert,
+    Set,
+    Delete,
+    Partition,
+    If,
+    Not,
+    Exists,
+    Select,
+    Scan,
+    Filter,
+    Limit,
+    After,
+    Integer,
+    String,
+    Null,
+    Binary,
+    Timestamp,
+    Decimal,
+    Add,
+    With,
+    Lenient,
+    Schema,
+    Float,
+    Boolean,
+    Duration,
+    List,
+    Map,
+    Number,
+    AttributeExists,
+    AttributeNotExists,
+    Begin,
+    Commit,
+    Rollback,
+    Transaction
+}
+
+// Every keyword, matched regardless of case. Only some are reserved, see `Keyword::is_reserved`.
+const KEYWORDS: &[(&str, Keyword)] = &[
+    ("GET", Keyword::Get),
+    ("PUT", Keyword::Put),
+    ("QUERY", Keyword::Query),
+    ("SCAN", Keyword::Scan),
+    ("UPDATE", Keyword::Update),
+    ("UPSERT", Keyword::Upsert),
+    ("DELETE", Keyword::Delete),
+    ("CREATE", Keyword::Create),
+    ("DROP", Keyword::Drop),
+    ("STORE", Keyword::Store),
+    ("HASH", Keyword::Hash),
+    ("SORT", Keyword::Sort),
+    ("KEY", Keyword::Key),
+    ("WITH", Keyword::With),
+    ("LENIENT", Keyword::Lenient),
+    ("SCHEMA", Keyword::Schema),
+    ("SELECT", Keyword::Select),
+    ("WHERE", Keyword::Where),
+    ("FILTER", Keyword::Filter),
+    ("LIMIT", Keyword::Limit),
+    ("AFTER", Keyword::After),
+    ("SET", Keyword::Set),
+    ("ADD", Keyword::Add),
+    ("PARTITION", Keyword::Partition),
+    ("IF", Keyword::If),
+    ("EXISTS", Keyword::Exists),
+    ("AND", Keyword::And),
+    ("OR", Keyword::Or),
+    ("NOT", Keyword::Not),
+    ("BETWEEN", Keyword::Between),
+    ("BEGINS_WITH", Keyword::BeginsWith),
+    ("ATTRIBUTE_EXISTS", Keyword::AttributeExists),
+    ("ATTRIBUTE_NOT_EXISTS", Keyword::AttributeNotExists),
+    ("TRUE", Keyword::True),
+    ("FALSE", Keyword::False),
+    ("NULL", Keyword::Null),
+    ("INTEGER", Keyword::Integer),
+    ("FLOAT", Keyword::Float),
+    ("DECIMAL", Keyword::Decimal),
+    ("NUMBER", Keyword::Number),
+    ("STRING", Keyword::String),
+    ("BOOLEAN", Keyword::Boolean),
+    ("BINARY", Keyword::Binary),
+    ("TIMESTAMP", Keyword::Timestamp),
+    ("DURATION", Keyword::Duration),
+    ("LIST", Keyword::List),
+    ("MAP", Keyword::Map),
+    ("BEGIN", Keyword::Begin),
+    ("COMMIT", Keyword::Commit),
+    ("ROLLBACK", Keyword::Rollback),
+    ("TRANSACTION", Keyword::Transaction)
+];
+
+impl Keyword {
+    pub fn from_word(word: &str) -> Option<Keyword> {
+        return KEYWORDS.iter().find(|(name, _)| name.eq_ignore_ascii_case(word)).map(|(_, keyword)| *keyword);
+    }
+
+    // Statement keywords and the literals TRUE, FALSE and NULL are reserved, so they are always lexed as
+    // keywords. The rest, such as types and clauses, are lexed as identifiers and only act as keywords
+    // where the grammar expects one, so `key` or `timestamp` remain usable as attribute names.
+    pub fn is_reserved(self) -> bool {
+        return matches!(
+            self,
+            Keyword::Get
+                | Keyword::Put
+                | Keyword::Query
+                | Keyword::Scan
+                | Keyword::Update
+                | Keyword::Upsert
+                | Keyword::Delete
+                | Keyword::Create
+                | Keyword::Drop
+                | Keyword::Begin
+                | Keyword::Commit
+                | Keyword::Rollback
+                | Keyword::True
+                | Keyword::False
+                | Keyword::Null
+        );
+    }
 }
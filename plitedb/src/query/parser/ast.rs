@@ -1,19 +1,193 @@
-use crate::query::lexer::token::{Token, TokenType};
+use std::fmt;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::query::{
+    cursor::Location,
+    lexer::token::{Token, TokenType}
+};
 
 use super::error::ParserError;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Statement {
-    PutItem { store_name: String, assignments: Vec<Assignment> },
-    GetItem { store_name: String, comparisons: Vec<Comparison> }
+    PutItem {
+        store_name: String,
+        assignments: Vec<Assignment>,
+        condition: Option<WriteCondition>
+    },
+    GetItem {
+        store_name: String,
+        projection: Option<Vec<AttributePath>>,
+        predicate: Predicate
+    },
+    Query {
+        store_name: String,
+        predicate: Predicate
+    },
+    Scan {
+        store_name: String,
+        filter: Option<Predicate>,
+        limit: Option<usize>,
+        start_after: Option<Vec<Assignment>>
+    },
+    UpdateItem {
+        store_name: String,
+        predicate: Predicate,
+        assignments: Vec<Assignment>,
+        additions: Vec<Assignment>,
+        deletions: Vec<Assignment>,
+        upsert: bool,
+        condition: Option<WriteCondition>
+    },
+    DeleteItem {
+        store_name: String,
+        predicate: Predicate,
+        entire_partition: bool,
+        condition: Option<WriteCondition>
+    },
+    CreateStore {
+        store_name: String,
+        hash_key: KeyDefinition,
+        sort_key: Option<KeyDefinition>,
+        schema: Option<Schema>
+    },
+    DropStore {
+        store_name: String
+    },
+    Begin,
+    Commit,
+    Rollback
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Assignment {
+pub struct KeyDefinition {
+    pub name: String,
+    pub key_type: KeyType
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    Integer,
+    String,
+    Binary,
+    Timestamp,
+    Decimal
+}
+
+impl KeyType {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            KeyType::Integer => "integer",
+            KeyType::String => "string",
+            KeyType::Binary => "bytes",
+            KeyType::Timestamp => "timestamp",
+            KeyType::Decimal => "decimal"
+        };
+    }
+}
+
+// Declared attributes are optional unless marked NOT NULL. A lenient schema also accepts attributes it
+// does not declare.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    pub attributes: Vec<AttributeDefinition>,
+    pub lenient: bool
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeDefinition {
     pub name: String,
+    pub attribute_type: AttributeType,
+    pub not_null: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeType {
+    Integer,
+    Float,
+    Decimal,
+    String,
+    Boolean,
+    Binary,
+    Timestamp,
+    Duration,
+    List,
+    Map,
+    StringSet,
+    NumberSet,
+    BinarySet
+}
+
+impl AttributeType {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            AttributeType::Integer => "integer",
+            AttributeType::Float => "float",
+            AttributeType::Decimal => "decimal",
+            AttributeType::String => "string",
+            AttributeType::Boolean => "boolean",
+            AttributeType::Binary => "bytes",
+            AttributeType::Timestamp => "timestamp",
+            AttributeType::Duration => "duration",
+            AttributeType::List => "list",
+            AttributeType::Map => "map",
+            AttributeType::StringSet => "string set",
+            AttributeType::NumberSet => "number set",
+            AttributeType::BinarySet => "binary set"
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub path: AttributePath,
     pub value: Expression
 }
 
+// A path into an item's document, such as `address.city` or `tags[0]`. `name` is the top-level
+// attribute the path starts from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributePath {
+    pub name: String,
+    pub segments: Vec<PathSegment>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize)
+}
+
+impl AttributePath {
+    pub fn new(name: String) -> Self {
+        return AttributePath { name, segments: Vec::new() };
+    }
+
+    pub fn is_top_level(&self) -> bool {
+        return self.segments.is_empty();
+    }
+}
+
+impl fmt::Display for AttributePath {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter
+    ) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        for segment in &self.segments {
+            match segment {
+                PathSegment::Field(name) => write!(f, ".{}", name)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?
+            }
+        }
+
+        return Ok(());
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ComparisonOperator {
     GreaterThan,
@@ -41,16 +215,83 @@ impl TryFrom<&Token> for ComparisonOperator {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Comparison {
-    pub name: String,
-    pub operator: ComparisonOperator,
-    pub value: Expression
+pub enum Predicate {
+    Comparison {
+        left: Expression,
+        operator: ComparisonOperator,
+        right: Expression
+    },
+    Between {
+        value: Expression,
+        low: Expression,
+        high: Expression
+    },
+    BeginsWith {
+        value: Expression,
+        prefix: Expression
+    },
+    AttributeExists {
+        path: AttributePath
+    },
+    AttributeNotExists {
+        path: AttributePath
+    },
+    // Any other expression, which must evaluate to a boolean.
+    Expression(Expression),
+    Not(Box<Predicate>),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>)
+}
+
+impl Predicate {
+    // The operands of the top-level ANDs, in order. Key conditions are read from these.
+    pub fn conjuncts(&self) -> Vec<&Predicate> {
+        return match self {
+            Predicate::And(left, right) => {
+                let mut conjuncts = left.conjuncts();
+                conjuncts.extend(right.conjuncts());
+
+                conjuncts
+            },
+            predicate => vec![predicate]
+        };
+    }
+
+    // The attribute a comparison, BETWEEN, BEGINS_WITH or existence check constrains, when its subject
+    // is a plain attribute path.
+    pub fn subject(&self) -> Option<&AttributePath> {
+        return match self {
+            Predicate::Comparison {
+                left: Expression::Path(path), ..
+            }
+            | Predicate::Between {
+                value: Expression::Path(path), ..
+            }
+            | Predicate::BeginsWith {
+                value: Expression::Path(path), ..
+            }
+            | Predicate::AttributeExists { path }
+            | Predicate::AttributeNotExists { path } => Some(path),
+            _ => None
+        };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WriteCondition {
+    Exists,
+    NotExists,
+    Predicate(Predicate)
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expression {
     Literal(Value),
-    Identifier(String),
+    Path(AttributePath),
+    List(Vec<Expression>),
+    Map(Vec<(String, Expression)>),
+    Set(Vec<Expression>),
+    FunctionCall(FunctionCall),
     BinaryOperation(BinaryOperation),
     UnaryOperation(UnaryOperation)
 }
@@ -58,9 +299,21 @@ pub enum Expression {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Float(f64),
+    Decimal(BigDecimal),
     Integer(i64),
     String(String),
-    Boolean(bool)
+    Boolean(bool),
+    Bytes(Vec<u8>),
+    Timestamp(DateTime<Utc>),
+    Duration(TimeDelta),
+    Null
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionCall {
+    pub name: String,
+    pub arguments: Vec<Expression>,
+    pub location: Location
 }
 
 #[derive(Debug, Clone, PartialEq)]
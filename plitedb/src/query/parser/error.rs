@@ -4,14 +4,18 @@ use crate::query::lexer::token::Token;
 pub enum ParserError {
     #[error("Unexpected token '{:?}' at {}. Expected '{}' instead", .0.token_type, .0.location, .1)]
     UnexpectedToken(Token, String),
-    #[error("Invalid numerical value '{0}'. Must be a valid integer or float")]
-    InvalidNumericalValue(String),
     #[error("Invalid comparison operator '{:?}' at {}", .0.token_type, .0.location)]
     InvalidComparisonOperator(Token),
     #[error("Invalid mathematical operator '{:?}' at {}", .0.token_type, .0.location)]
     InvalidMathematicalOperator(Token),
     #[error("Invalid unary operator '{:?}' at {}", .0.token_type, .0.location)]
     InvalidUnaryOperator(Token),
+    #[error("Invalid limit '{:?}' at {}. Must be a positive integer", .0.token_type, .0.location)]
+    InvalidLimit(Token),
+    #[error("Integer '{:?}' at {} is out of range", .0.token_type, .0.location)]
+    IntegerOutOfRange(Token),
+    #[error("Invalid list index '{:?}' at {}. Must be a non-negative integer", .0.token_type, .0.location)]
+    InvalidIndex(Token),
     #[error("Missing identifier. Expected '{0}'")]
     MissingIdentifier(String),
     #[error("Unexpected end of input")]
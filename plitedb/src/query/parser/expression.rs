@@ -1,22 +1,29 @@
-use crate::query::cursor::PeekingCursor;
+use crate::query::cursor::{Location, PeekingCursor};
 use crate::query::lexer::token::{Keyword, Token, TokenType};
 
-use super::ast::{BinaryOperation, BinaryOperator, Expression, UnaryOperation, UnaryOperator, Value};
+use super::ast::{BinaryOperation, BinaryOperator, Expression, FunctionCall, UnaryOperation, UnaryOperator, Value};
 use super::error::{ParserError, ParserResult};
+use super::path::{parse_attribute_name, parse_path_segments};
 
 pub fn parse_expression<I: Iterator<Item = Token>>(
     tokens: &mut PeekingCursor<I>,
     precedence: u8
 ) -> ParserResult<Expression> {
-    let mut left = parse_primary(tokens)?;
+    let left = parse_primary(tokens)?;
 
-    loop {
-        let binary_operator = match tokens.peek() {
-            Some(token) => match BinaryOperator::try_from(token) {
-                Ok(operator) => operator,
-                Err(_) => break
-            },
-            _ => break
+    return parse_operations(tokens, left, precedence);
+}
+
+// Continues an expression whose leftmost operand has already been parsed.
+pub fn parse_operations<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    mut left: Expression,
+    precedence: u8
+) -> ParserResult<Expression> {
+    while let Some(token) = tokens.peek() {
+        let binary_operator = match BinaryOperator::try_from(token) {
+            Ok(operator) => operator,
+            Err(_) => break
         };
 
         if binary_operator.precedence() < precedence {
@@ -35,20 +42,37 @@ fn parse_primary<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> Pa
 
     return match token.token_type {
         TokenType::String(value) => Ok(Expression::Literal(Value::String(value))),
+        TokenType::Bytes(value) => Ok(Expression::Literal(Value::Bytes(value))),
+        TokenType::Timestamp(value) => Ok(Expression::Literal(Value::Timestamp(value))),
+        TokenType::Duration(value) => Ok(Expression::Literal(Value::Duration(value))),
         TokenType::Keyword(Keyword::True) => Ok(Expression::Literal(Value::Boolean(true))),
         TokenType::Keyword(Keyword::False) => Ok(Expression::Literal(Value::Boolean(false))),
-        TokenType::Number(number) => {
-            if let Ok(int_value) = number.parse::<i64>() {
-                Ok(Expression::Literal(Value::Integer(int_value)))
-            }
-            else if let Ok(float_value) = number.parse::<f64>() {
-                Ok(Expression::Literal(Value::Float(float_value)))
-            }
-            else {
-                Err(ParserError::InvalidNumericalValue(number))
-            }
+        TokenType::Keyword(Keyword::Null) => Ok(Expression::Literal(Value::Null)),
+        TokenType::Integer(value) => match i64::try_from(value) {
+            Ok(value) => Ok(Expression::Literal(Value::Integer(value))),
+            Err(_) => Err(ParserError::IntegerOutOfRange(Token::new(
+                TokenType::Integer(value),
+                token.location
+            )))
+        },
+        TokenType::Float(value) => Ok(Expression::Literal(Value::Float(value))),
+        TokenType::Decimal(value) => Ok(Expression::Literal(Value::Decimal(value))),
+        TokenType::Identifier(value) => match tokens.peek().map(|token| &token.token_type) {
+            Some(TokenType::LeftParenthesis) => parse_function_call(tokens, value, token.location),
+            _ => Ok(Expression::Path(parse_path_segments(tokens, value)?))
         },
-        TokenType::Identifier(value) => Ok(Expression::Identifier(value)),
+        TokenType::QuotedIdentifier(value) => Ok(Expression::Path(parse_path_segments(tokens, value)?)),
+        TokenType::LeftSquareBracket => Ok(Expression::List(parse_elements(
+            tokens,
+            TokenType::RightSquareBracket,
+            ", or ]"
+        )?)),
+        TokenType::LeftDoubleAngleBracket => Ok(Expression::Set(parse_elements(
+            tokens,
+            TokenType::RightDoubleAngleBracket,
+            ", or >>"
+        )?)),
+        TokenType::LeftCurlyBrace => parse_map(tokens),
         TokenType::LeftParenthesis => {
             let expression = parse_expression(tokens, 0)?;
 
@@ -63,6 +87,12 @@ fn parse_primary<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> Pa
 
             Ok(expression)
         },
+        // The magnitude of the smallest integer is out of range on its own, so it is negated as it is read.
+        TokenType::Minus if tokens.peek().map(|token| &token.token_type) == Some(&TokenType::Integer(i64::MIN.unsigned_abs())) => {
+            tokens.next();
+
+            Ok(Expression::Literal(Value::Integer(i64::MIN)))
+        },
         TokenType::Minus => {
             let expression = parse_primary(tokens)?;
 
@@ -75,12 +105,102 @@ fn parse_primary<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> Pa
     };
 }
 
+fn parse_function_call<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    name: String,
+    location: Location
+) -> ParserResult<Expression> {
+    match tokens.next() {
+        Some(token) => match token.token_type {
+            TokenType::LeftParenthesis => (),
+            _ => return Err(ParserError::UnexpectedToken(token, "(".to_string()))
+        },
+        None => return Err(ParserError::UnexpectedEndOfInput)
+    }
+
+    return Ok(Expression::FunctionCall(FunctionCall {
+        name,
+        arguments: parse_elements(tokens, TokenType::RightParenthesis, ", or )")?,
+        location
+    }));
+}
+
+// Parses comma separated elements up to the closing token, which is consumed. A trailing comma is allowed.
+fn parse_elements<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    closing: TokenType,
+    expected: &str
+) -> ParserResult<Vec<Expression>> {
+    let mut elements = Vec::new();
+
+    loop {
+        match tokens.peek().map(|token| &token.token_type) {
+            Some(token_type) if *token_type == closing => {
+                tokens.next();
+                break;
+            },
+            Some(_) => elements.push(parse_expression(tokens, 0)?),
+            None => return Err(ParserError::UnexpectedEndOfInput)
+        }
+
+        match tokens.next() {
+            Some(token) if token.token_type == TokenType::Comma => (),
+            Some(token) if token.token_type == closing => break,
+            Some(token) => return Err(ParserError::UnexpectedToken(token, expected.to_string())),
+            None => return Err(ParserError::UnexpectedEndOfInput)
+        }
+    }
+
+    return Ok(elements);
+}
+
+fn parse_map<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Expression> {
+    let mut entries = Vec::new();
+
+    loop {
+        match tokens.peek().map(|token| &token.token_type) {
+            Some(TokenType::RightCurlyBrace) => {
+                tokens.next();
+                break;
+            },
+            Some(_) => {
+                let name = parse_attribute_name(tokens)?;
+
+                match tokens.next() {
+                    Some(token) => match token.token_type {
+                        TokenType::Colon => (),
+                        _ => return Err(ParserError::UnexpectedToken(token, ":".to_string()))
+                    },
+                    None => return Err(ParserError::UnexpectedEndOfInput)
+                }
+
+                entries.push((name, parse_expression(tokens, 0)?));
+            },
+            None => return Err(ParserError::UnexpectedEndOfInput)
+        }
+
+        match tokens.next() {
+            Some(token) => match token.token_type {
+                TokenType::Comma => (),
+                TokenType::RightCurlyBrace => break,
+                _ => return Err(ParserError::UnexpectedToken(token, ", or }".to_string()))
+            },
+            None => return Err(ParserError::UnexpectedEndOfInput)
+        }
+    }
+
+    return Ok(Expression::Map(entries));
+}
+
 fn parse_binary_operation<I: Iterator<Item = Token>>(
     tokens: &mut PeekingCursor<I>,
     left: Expression,
     operator: BinaryOperator
 ) -> ParserResult<Expression> {
-    let precedence = operator.precedence();
+    let precedence = match operator {
+        BinaryOperator::Exponentiate => operator.precedence(),
+        _ => operator.precedence() + 1
+    };
     let right = parse_expression(tokens, precedence)?;
 
     return Ok(Expression::BinaryOperation(BinaryOperation {
@@ -92,19 +212,23 @@ fn parse_binary_operation<I: Iterator<Item = Token>>(
 
 #[cfg(test)]
 mod tests {
-    use crate::query::{cursor::Location, lexer::token::TokenType};
+    use crate::query::{
+        cursor::Location,
+        lexer::token::TokenType,
+        parser::ast::{AttributePath, PathSegment}
+    };
 
     use super::*;
 
     #[test]
     fn parses_basic_math_equation() {
         // 1 + 2 + 3
-        let token_types = vec![
-            TokenType::Number("1".to_string()),
+        let token_types = [
+            TokenType::Integer(1),
             TokenType::Plus,
-            TokenType::Number("2".to_string()),
+            TokenType::Integer(2),
             TokenType::Plus,
-            TokenType::Number("3".to_string()),
+            TokenType::Integer(3)
         ];
 
         let tokens: Vec<Token> = token_types
@@ -133,14 +257,14 @@ mod tests {
     #[test]
     fn parses_basic_parenthetical_math_equation() {
         // 1 * (2 + 3)
-        let token_types = vec![
-            TokenType::Number("1".to_string()),
+        let token_types = [
+            TokenType::Integer(1),
             TokenType::Asterisk,
             TokenType::LeftParenthesis,
-            TokenType::Number("2".to_string()),
+            TokenType::Integer(2),
             TokenType::Plus,
-            TokenType::Number("3".to_string()),
-            TokenType::RightParenthesis,
+            TokenType::Integer(3),
+            TokenType::RightParenthesis
         ];
 
         let tokens: Vec<Token> = token_types
@@ -169,18 +293,18 @@ mod tests {
     #[test]
     fn parses_unary_expression() {
         // 1 + -1 - (1 * -5)
-        let token_types = vec![
-            TokenType::Number("1".to_string()),
+        let token_types = [
+            TokenType::Integer(1),
             TokenType::Plus,
             TokenType::Minus,
-            TokenType::Number("1".to_string()),
+            TokenType::Integer(1),
             TokenType::Minus,
             TokenType::LeftParenthesis,
-            TokenType::Number("1".to_string()),
+            TokenType::Integer(1),
             TokenType::Asterisk,
             TokenType::Minus,
-            TokenType::Number("5".to_string()),
-            TokenType::RightParenthesis,
+            TokenType::Integer(5),
+            TokenType::RightParenthesis
         ];
 
         let tokens: Vec<Token> = token_types
@@ -195,21 +319,21 @@ mod tests {
         assert_eq!(
             ast,
             Expression::BinaryOperation(BinaryOperation {
-                left: Box::new(Expression::Literal(Value::Integer(1))),
-                operator: BinaryOperator::Add,
-                right: Box::new(Expression::BinaryOperation(BinaryOperation {
-                    left: Box::new(Expression::UnaryOperation(UnaryOperation {
+                left: Box::new(Expression::BinaryOperation(BinaryOperation {
+                    left: Box::new(Expression::Literal(Value::Integer(1))),
+                    operator: BinaryOperator::Add,
+                    right: Box::new(Expression::UnaryOperation(UnaryOperation {
                         operator: UnaryOperator::Negate,
                         operand: Box::new(Expression::Literal(Value::Integer(1)))
-                    })),
-                    operator: BinaryOperator::Subtract,
-                    right: Box::new(Expression::BinaryOperation(BinaryOperation {
-                        left: Box::new(Expression::Literal(Value::Integer(1))),
-                        operator: BinaryOperator::Multiply,
-                        right: Box::new(Expression::UnaryOperation(UnaryOperation {
-                            operator: UnaryOperator::Negate,
-                            operand: Box::new(Expression::Literal(Value::Integer(5)))
-                        }))
+                    }))
+                })),
+                operator: BinaryOperator::Subtract,
+                right: Box::new(Expression::BinaryOperation(BinaryOperation {
+                    left: Box::new(Expression::Literal(Value::Integer(1))),
+                    operator: BinaryOperator::Multiply,
+                    right: Box::new(Expression::UnaryOperation(UnaryOperation {
+                        operator: UnaryOperator::Negate,
+                        operand: Box::new(Expression::Literal(Value::Integer(5)))
                     }))
                 }))
             })
@@ -219,30 +343,30 @@ mod tests {
     #[test]
     fn parses_complex_expression() {
         // ( (5 - 2) / 2) + ( 2 + ( 9 * 4 - 2 ) / 2 )
-        let token_types = vec![
+        let token_types = [
             TokenType::LeftParenthesis,
             TokenType::LeftParenthesis,
-            TokenType::Number("5".to_string()),
+            TokenType::Integer(5),
             TokenType::Minus,
-            TokenType::Number("2".to_string()),
+            TokenType::Integer(2),
             TokenType::RightParenthesis,
             TokenType::Slash,
-            TokenType::Number("2".to_string()),
+            TokenType::Integer(2),
             TokenType::RightParenthesis,
             TokenType::Plus,
             TokenType::LeftParenthesis,
-            TokenType::Number("2".to_string()),
+            TokenType::Integer(2),
             TokenType::Plus,
             TokenType::LeftParenthesis,
-            TokenType::Number("9".to_string()),
+            TokenType::Integer(9),
             TokenType::Asterisk,
-            TokenType::Number("4".to_string()),
+            TokenType::Integer(4),
             TokenType::Minus,
-            TokenType::Number("2".to_string()),
+            TokenType::Integer(2),
             TokenType::RightParenthesis,
             TokenType::Slash,
-            TokenType::Number("2".to_string()),
-            TokenType::RightParenthesis,
+            TokenType::Integer(2),
+            TokenType::RightParenthesis
         ];
 
         let tokens: Vec<Token> = token_types
@@ -287,4 +411,51 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn parses_collection_literals() {
+        // [1, { a: tags[0], }, []]
+        let token_types = [
+            TokenType::LeftSquareBracket,
+            TokenType::Integer(1),
+            TokenType::Comma,
+            TokenType::LeftCurlyBrace,
+            TokenType::Identifier("a".to_string()),
+            TokenType::Colon,
+            TokenType::Identifier("tags".to_string()),
+            TokenType::LeftSquareBracket,
+            TokenType::Integer(0),
+            TokenType::RightSquareBracket,
+            TokenType::Comma,
+            TokenType::RightCurlyBrace,
+            TokenType::Comma,
+            TokenType::LeftSquareBracket,
+            TokenType::RightSquareBracket,
+            TokenType::RightSquareBracket
+        ];
+
+        let tokens: Vec<Token> = token_types
+            .iter()
+            .zip(1..)
+            .map(|(token_type, column)| Token::new(token_type.clone(), Location { line: 1, column }))
+            .collect();
+
+        let mut cursor = PeekingCursor::new(tokens.into_iter());
+        let ast = parse_expression(&mut cursor, 0).unwrap();
+
+        assert_eq!(
+            ast,
+            Expression::List(vec![
+                Expression::Literal(Value::Integer(1)),
+                Expression::Map(vec![(
+                    "a".to_string(),
+                    Expression::Path(AttributePath {
+                        name: "tags".to_string(),
+                        segments: vec![PathSegment::Index(0)]
+                    })
+                )]),
+                Expression::List(vec![])
+            ])
+        );
+    }
 }
@@ -1,15 +1,17 @@
 use ast::Statement;
-use error::ParserResult;
+use error::{ParserError, ParserResult};
 use statement::parse_statement;
 
 use super::{
     cursor::{CursorTrackable, Location, PeekingCursor},
-    lexer::token::Token
+    lexer::token::{Keyword, Token, TokenType}
 };
 
 pub mod ast;
 pub mod error;
 pub mod expression;
+pub mod path;
+pub mod predicate;
 pub mod statement;
 
 impl CursorTrackable for Token {
@@ -21,8 +23,83 @@ impl CursorTrackable for Token {
     }
 }
 
+// Parses a single statement, which may be followed by a semicolon but nothing else.
 pub fn parse<I: IntoIterator<Item = Token>>(tokens: I) -> ParserResult<Statement> {
     let mut tokens = PeekingCursor::new(tokens.into_iter());
 
-    return parse_statement(&mut tokens);
+    let statement = parse_statement(&mut tokens)?;
+
+    if let Some(TokenType::Semicolon) = tokens.peek().map(|token| &token.token_type) {
+        tokens.next();
+    }
+
+    return match tokens.next() {
+        Some(token) => Err(ParserError::UnexpectedToken(token, "end of input".to_string())),
+        None => Ok(statement)
+    };
+}
+
+// Parses statements separated by semicolons. Empty statements are skipped, so a script may end with a
+// semicolon or contain none at all.
+pub fn parse_script<I: IntoIterator<Item = Token>>(tokens: I) -> ParserResult<Vec<Statement>> {
+    let mut tokens = PeekingCursor::new(tokens.into_iter());
+    let mut statements = Vec::new();
+
+    loop {
+        while let Some(TokenType::Semicolon) = tokens.peek().map(|token| &token.token_type) {
+            tokens.next();
+        }
+
+        if tokens.peek().is_none() {
+            break;
+        }
+
+        statements.push(parse_statement(&mut tokens)?);
+
+        match tokens.next() {
+            Some(token) => match token.token_type {
+                TokenType::Semicolon => (),
+                _ => return Err(ParserError::UnexpectedToken(token, "; or end of input".to_string()))
+            },
+            None => break
+        }
+    }
+
+    return Ok(statements);
+}
+
+// Reserved words are lexed as keywords, while the others arrive as identifiers and are only keywords
+// where the grammar expects them, so both are recognized here. Quoted identifiers never are.
+fn keyword_of(token: &Token) -> Option<Keyword> {
+    return match &token.token_type {
+        TokenType::Keyword(keyword) => Some(*keyword),
+        TokenType::Identifier(word) => Keyword::from_word(word),
+        _ => None
+    };
+}
+
+// Consumes the next token if it is the given keyword.
+fn take_keyword<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    keyword: Keyword
+) -> bool {
+    return match tokens.peek() {
+        Some(token) if keyword_of(token) == Some(keyword) => {
+            tokens.next();
+            true
+        },
+        _ => false
+    };
+}
+
+fn expect_keyword<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    keyword: Keyword,
+    expected: &str
+) -> ParserResult<()> {
+    return match tokens.next() {
+        Some(token) if keyword_of(&token) == Some(keyword) => Ok(()),
+        Some(token) => Err(ParserError::UnexpectedToken(token, expected.to_string())),
+        None => Err(ParserError::UnexpectedEndOfInput)
+    };
 }
@@ -0,0 +1,100 @@
+use crate::query::{
+    cursor::PeekingCursor,
+    lexer::token::{Token, TokenType}
+};
+
+use super::{
+    ast::{AttributePath, PathSegment},
+    error::{ParserError, ParserResult}
+};
+
+pub fn parse_attribute_path<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<AttributePath> {
+    let name = parse_attribute_name(tokens)?;
+
+    return parse_path_segments(tokens, name);
+}
+
+pub fn parse_path_segments<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    name: String
+) -> ParserResult<AttributePath> {
+    let mut path = AttributePath::new(name);
+
+    while let Some(token) = tokens.peek() {
+        match token.token_type {
+            TokenType::Period => {
+                tokens.next();
+                path.segments.push(PathSegment::Field(parse_attribute_name(tokens)?));
+            },
+            TokenType::LeftSquareBracket => {
+                tokens.next();
+                path.segments.push(PathSegment::Index(parse_index(tokens)?));
+            },
+            _ => break
+        }
+    }
+
+    return Ok(path);
+}
+
+pub fn parse_attribute_name<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<String> {
+    return match tokens.next() {
+        Some(token) => match token.token_type {
+            TokenType::Identifier(name) | TokenType::QuotedIdentifier(name) | TokenType::String(name) => Ok(name),
+            _ => Err(ParserError::UnexpectedToken(
+                token,
+                "attribute name, as a string or identifier".to_string()
+            ))
+        },
+        None => Err(ParserError::UnexpectedEndOfInput)
+    };
+}
+
+fn parse_index<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<usize> {
+    let token = tokens.next().ok_or(ParserError::UnexpectedEndOfInput)?;
+
+    let index = match &token.token_type {
+        TokenType::Integer(index) => usize::try_from(*index).map_err(|_| ParserError::InvalidIndex(token))?,
+        TokenType::Float(_) | TokenType::Decimal(_) => return Err(ParserError::InvalidIndex(token)),
+        _ => return Err(ParserError::UnexpectedToken(token, "list index".to_string()))
+    };
+
+    return match tokens.next() {
+        Some(token) => match token.token_type {
+            TokenType::RightSquareBracket => Ok(index),
+            _ => Err(ParserError::UnexpectedToken(token, "]".to_string()))
+        },
+        None => Err(ParserError::UnexpectedEndOfInput)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::query::lexer::tokenize;
+
+    use super::*;
+
+    fn parse_input(input: &str) -> ParserResult<AttributePath> {
+        let mut tokens = PeekingCursor::new(tokenize(input).unwrap().into_iter());
+
+        return parse_attribute_path(&mut tokens);
+    }
+
+    #[test]
+    fn parses_document_paths() {
+        let path = parse_input("address.city").unwrap();
+        assert_eq!(path.name, "address");
+        assert_eq!(path.segments, [PathSegment::Field(String::from("city"))]);
+
+        let path = parse_input("orders[2].'line items'[0]").unwrap();
+        assert_eq!(
+            path.segments,
+            [PathSegment::Index(2), PathSegment::Field(String::from("line items")), PathSegment::Index(0)]
+        );
+        assert_eq!(path.to_string(), "orders[2].line items[0]");
+
+        assert!(parse_input("tags").unwrap().is_top_level());
+        assert!(matches!(parse_input("tags[1.5]"), Err(ParserError::InvalidIndex(_))));
+        assert!(matches!(parse_input("tags[0"), Err(ParserError::UnexpectedEndOfInput)));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::query::{
+    cursor::PeekingCursor,
+    lexer::token::{Keyword, Token, TokenType}
+};
+
+use super::{
+    ast::{AttributePath, ComparisonOperator, Expression, Predicate},
+    error::{ParserError, ParserResult},
+    expect_keyword,
+    expression::{parse_expression, parse_operations},
+    keyword_of,
+    path::parse_attribute_path,
+    take_keyword
+};
+
+// NOT binds tighter than AND, which binds tighter than OR. Commas separate conditions like AND does,
+// but bind loosest of all.
+pub fn parse_predicate<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Predicate> {
+    return parse_conditions(tokens, None);
+}
+
+// Continues a predicate whose leading NOT has already been consumed.
+pub fn parse_negated_predicate<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Predicate> {
+    let negation = Predicate::Not(Box::new(parse_negation(tokens)?));
+
+    return parse_conditions(tokens, Some(negation));
+}
+
+fn parse_conditions<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    leading: Option<Predicate>
+) -> ParserResult<Predicate> {
+    let mut predicate = parse_disjunction(tokens, leading)?;
+
+    while let Some(TokenType::Comma) = tokens.peek().map(|token| &token.token_type) {
+        tokens.next();
+        predicate = Predicate::And(Box::new(predicate), Box::new(parse_disjunction(tokens, None)?));
+    }
+
+    return Ok(predicate);
+}
+
+fn parse_disjunction<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    leading: Option<Predicate>
+) -> ParserResult<Predicate> {
+    let mut predicate = parse_conjunction(tokens, leading)?;
+
+    while take_keyword(tokens, Keyword::Or) {
+        predicate = Predicate::Or(Box::new(predicate), Box::new(parse_conjunction(tokens, None)?));
+    }
+
+    return Ok(predicate);
+}
+
+fn parse_conjunction<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    leading: Option<Predicate>
+) -> ParserResult<Predicate> {
+    let mut predicate = match leading {
+        Some(predicate) => predicate,
+        None => parse_negation(tokens)?
+    };
+
+    while take_keyword(tokens, Keyword::And) {
+        predicate = Predicate::And(Box::new(predicate), Box::new(parse_negation(tokens)?));
+    }
+
+    return Ok(predicate);
+}
+
+fn parse_negation<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Predicate> {
+    if take_keyword(tokens, Keyword::Not) {
+        return Ok(Predicate::Not(Box::new(parse_negation(tokens)?)));
+    }
+
+    return match tokens.peek() {
+        Some(token) if keyword_of(token) == Some(Keyword::AttributeExists) => {
+            tokens.next();
+
+            Ok(Predicate::AttributeExists {
+                path: parse_parenthesized_attribute_path(tokens)?
+            })
+        },
+        Some(token) if keyword_of(token) == Some(Keyword::AttributeNotExists) => {
+            tokens.next();
+
+            Ok(Predicate::AttributeNotExists {
+                path: parse_parenthesized_attribute_path(tokens)?
+            })
+        },
+        // A parenthesis either groups a predicate or starts an expression such as `(a + b) * 2 > c`,
+        // which is only known once the group is closed.
+        Some(token) if token.token_type == TokenType::LeftParenthesis => {
+            tokens.next();
+            let predicate = parse_disjunction(tokens, None)?;
+            expect_right_parenthesis(tokens)?;
+
+            match predicate {
+                Predicate::Expression(expression) => {
+                    let value = parse_operations(tokens, expression, 0)?;
+
+                    parse_comparison(tokens, value)
+                },
+                predicate => Ok(predicate)
+            }
+        },
+        _ => {
+            let value = parse_expression(tokens, 0)?;
+
+            parse_comparison(tokens, value)
+        }
+    };
+}
+
+fn parse_comparison<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    value: Expression
+) -> ParserResult<Predicate> {
+    let Some(token) = tokens.peek()
+    else {
+        return Ok(Predicate::Expression(value));
+    };
+
+    if let Ok(operator) = ComparisonOperator::try_from(token) {
+        tokens.next();
+
+        return Ok(Predicate::Comparison {
+            left: value,
+            operator,
+            right: parse_expression(tokens, 0)?
+        });
+    }
+
+    let predicate = match keyword_of(token) {
+        Some(Keyword::Between) => {
+            tokens.next();
+
+            let low = parse_expression(tokens, 0)?;
+            expect_keyword(tokens, Keyword::And, "AND")?;
+            let high = parse_expression(tokens, 0)?;
+
+            Predicate::Between { value, low, high }
+        },
+        Some(Keyword::BeginsWith) => {
+            tokens.next();
+
+            Predicate::BeginsWith {
+                value,
+                prefix: parse_expression(tokens, 0)?
+            }
+        },
+        _ => Predicate::Expression(value)
+    };
+
+    return Ok(predicate);
+}
+
+fn parse_parenthesized_attribute_path<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<AttributePath> {
+    match tokens.next() {
+        Some(token) => match token.token_type {
+            TokenType::LeftParenthesis => (),
+            _ => return Err(ParserError::UnexpectedToken(token, "(".to_string()))
+        },
+        None => return Err(ParserError::UnexpectedEndOfInput)
+    }
+
+    let path = parse_attribute_path(tokens)?;
+    expect_right_parenthesis(tokens)?;
+
+    return Ok(path);
+}
+
+fn expect_right_parenthesis<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<()> {
+    return match tokens.next() {
+        Some(token) => match token.token_type {
+            TokenType::RightParenthesis => Ok(()),
+            _ => Err(ParserError::UnexpectedToken(token, ")".to_string()))
+        },
+        None => Err(ParserError::UnexpectedEndOfInput)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::query::{
+        lexer::tokenize,
+        parser::ast::{BinaryOperation, BinaryOperator, Value}
+    };
+
+    use super::*;
+
+    fn parse(input: &str) -> Predicate {
+        let mut cursor = PeekingCursor::new(tokenize(input).unwrap().into_iter());
+
+        return parse_predicate(&mut cursor).unwrap();
+    }
+
+    fn path(name: &str) -> Expression {
+        return Expression::Path(AttributePath::new(name.to_string()));
+    }
+
+    fn comparison(
+        left: Expression,
+        operator: ComparisonOperator,
+        right: Expression
+    ) -> Predicate {
+        return Predicate::Comparison { left, operator, right };
+    }
+
+    #[test]
+    fn parses_boolean_logic_by_precedence() {
+        let age = comparison(
+            path("age"),
+            ComparisonOperator::GreaterThan,
+            Expression::Literal(Value::Integer(30))
+        );
+        let vip = comparison(
+            path("vip"),
+            ComparisonOperator::Equal,
+            Expression::Literal(Value::Boolean(true))
+        );
+        let banned = Predicate::Not(Box::new(Predicate::Expression(path("banned"))));
+
+        assert_eq!(
+            parse("(age > 30 OR vip = true) AND NOT banned"),
+            Predicate::And(
+                Box::new(Predicate::Or(Box::new(age.clone()), Box::new(vip.clone()))),
+                Box::new(banned.clone())
+            )
+        );
+        assert_eq!(
+            parse("age > 30 OR vip = true AND NOT banned"),
+            Predicate::Or(
+                Box::new(age.clone()),
+                Box::new(Predicate::And(Box::new(vip.clone()), Box::new(banned.clone())))
+            )
+        );
+        assert_eq!(
+            parse("age > 30 OR vip = true, NOT banned"),
+            Predicate::And(Box::new(Predicate::Or(Box::new(age), Box::new(vip))), Box::new(banned))
+        );
+    }
+
+    #[test]
+    fn parses_expressions_on_both_sides_of_comparisons() {
+        // The parenthesis opens an expression rather than a group of conditions.
+        assert_eq!(
+            parse("(a + b) * 2 <= c - 1"),
+            comparison(
+                Expression::BinaryOperation(BinaryOperation {
+                    left: Box::new(Expression::BinaryOperation(BinaryOperation {
+                        left: Box::new(path("a")),
+                        operator: BinaryOperator::Add,
+                        right: Box::new(path("b"))
+                    })),
+                    operator: BinaryOperator::Multiply,
+                    right: Box::new(Expression::Literal(Value::Integer(2)))
+                }),
+                ComparisonOperator::LessThanOrEqual,
+                Expression::BinaryOperation(BinaryOperation {
+                    left: Box::new(path("c")),
+                    operator: BinaryOperator::Subtract,
+                    right: Box::new(Expression::Literal(Value::Integer(1)))
+                })
+            )
+        );
+        assert_eq!(
+            parse("a BETWEEN 1 AND 5 AND b BEGINS_WITH 'x'"),
+            Predicate::And(
+                Box::new(Predicate::Between {
+                    value: path("a"),
+                    low: Expression::Literal(Value::Integer(1)),
+                    high: Expression::Literal(Value::Integer(5))
+                }),
+                Box::new(Predicate::BeginsWith {
+                    value: path("b"),
+                    prefix: Expression::Literal(Value::String("x".to_string()))
+                })
+            )
+        );
+    }
+}
@@ -4,35 +4,60 @@ use crate::query::{
 };
 
 use super::{
-    ast::{Assignment, Comparison, ComparisonOperator, Statement},
+    ast::{Assignment, AttributeDefinition, AttributePath, AttributeType, KeyDefinition, KeyType, Schema, Statement, WriteCondition},
     error::{ParserError, ParserResult},
-    expression::parse_expression
+    expect_keyword,
+    expression::parse_expression,
+    keyword_of,
+    path::{parse_attribute_name, parse_attribute_path},
+    predicate::{parse_negated_predicate, parse_predicate},
+    take_keyword
 };
 
 pub fn parse_statement<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Statement> {
-    let statement = match tokens.next() {
+    return match tokens.next() {
         Some(token) => match token.token_type {
             TokenType::Keyword(Keyword::Get) => parse_get_item(tokens),
             TokenType::Keyword(Keyword::Put) => parse_put_item(tokens),
-            _ => Err(ParserError::UnexpectedToken(token, "GET or PUT".to_string()))
+            TokenType::Keyword(Keyword::Query) => parse_query(tokens),
+            TokenType::Keyword(Keyword::Scan) => parse_scan(tokens),
+            TokenType::Keyword(Keyword::Update) => parse_update_item(tokens, false),
+            TokenType::Keyword(Keyword::Upsert) => parse_update_item(tokens, true),
+            TokenType::Keyword(Keyword::Delete) => parse_delete_item(tokens),
+            TokenType::Keyword(Keyword::Create) => parse_create_store(tokens),
+            TokenType::Keyword(Keyword::Drop) => parse_drop_store(tokens),
+            TokenType::Keyword(Keyword::Begin) => parse_transaction_control(tokens, Statement::Begin),
+            TokenType::Keyword(Keyword::Commit) => parse_transaction_control(tokens, Statement::Commit),
+            TokenType::Keyword(Keyword::Rollback) => parse_transaction_control(tokens, Statement::Rollback),
+            _ => Err(ParserError::UnexpectedToken(
+                token,
+                "GET, PUT, QUERY, SCAN, UPDATE, UPSERT, DELETE, CREATE, DROP, BEGIN, COMMIT or ROLLBACK".to_string()
+            ))
         },
         None => Err(ParserError::UnexpectedEndOfInput)
     };
-
-    return Ok(statement?);
 }
 
 fn parse_get_item<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Statement> {
     let store_name = parse_store_name(tokens)?;
 
+    let projection = match take_keyword(tokens, Keyword::Select) {
+        true => Some(parse_projection(tokens)?),
+        false => None
+    };
+
     expect_where(tokens)?;
     expect_left_curly_brace(tokens)?;
 
-    let comparisons = parse_comparisons(tokens)?;
+    let predicate = parse_predicate(tokens)?;
 
     expect_right_curly_brace(tokens)?;
 
-    return Ok(Statement::GetItem { store_name, comparisons });
+    return Ok(Statement::GetItem {
+        store_name,
+        projection,
+        predicate
+    });
 }
 
 fn parse_put_item<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Statement> {
@@ -44,44 +69,320 @@ fn parse_put_item<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> P
 
     expect_right_curly_brace(tokens)?;
 
-    return Ok(Statement::PutItem { store_name, assignments });
+    let condition = parse_write_condition(tokens)?;
+
+    return Ok(Statement::PutItem {
+        store_name,
+        assignments,
+        condition
+    });
+}
+
+fn parse_query<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Statement> {
+    let store_name = parse_store_name(tokens)?;
+
+    expect_where(tokens)?;
+    expect_left_curly_brace(tokens)?;
+
+    let predicate = parse_predicate(tokens)?;
+
+    expect_right_curly_brace(tokens)?;
+
+    return Ok(Statement::Query { store_name, predicate });
+}
+
+fn parse_scan<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Statement> {
+    let store_name = parse_store_name(tokens)?;
+
+    let filter = match take_keyword(tokens, Keyword::Filter) {
+        true => {
+            expect_left_curly_brace(tokens)?;
+            let predicate = parse_predicate(tokens)?;
+            expect_right_curly_brace(tokens)?;
+
+            Some(predicate)
+        },
+        false => None
+    };
+
+    let limit = match take_keyword(tokens, Keyword::Limit) {
+        true => Some(parse_limit(tokens)?),
+        false => None
+    };
+
+    let start_after = match take_keyword(tokens, Keyword::After) {
+        true => {
+            expect_left_curly_brace(tokens)?;
+            let assignments = parse_assignments(tokens)?;
+            expect_right_curly_brace(tokens)?;
+
+            Some(assignments)
+        },
+        false => None
+    };
+
+    return Ok(Statement::Scan {
+        store_name,
+        filter,
+        limit,
+        start_after
+    });
+}
+
+fn parse_update_item<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    upsert: bool
+) -> ParserResult<Statement> {
+    let store_name = parse_store_name(tokens)?;
+
+    expect_where(tokens)?;
+    expect_left_curly_brace(tokens)?;
+
+    let predicate = parse_predicate(tokens)?;
+
+    expect_right_curly_brace(tokens)?;
+
+    let assignments = match take_keyword(tokens, Keyword::Set) {
+        true => {
+            expect_left_curly_brace(tokens)?;
+            let assignments = parse_assignments(tokens)?;
+            expect_right_curly_brace(tokens)?;
+
+            assignments
+        },
+        false => Vec::new()
+    };
+
+    let additions = match take_keyword(tokens, Keyword::Add) {
+        true => parse_set_actions(tokens)?,
+        false => Vec::new()
+    };
+
+    let deletions = match take_keyword(tokens, Keyword::Delete) {
+        true => parse_set_actions(tokens)?,
+        false => Vec::new()
+    };
+
+    if assignments.is_empty() && additions.is_empty() && deletions.is_empty() {
+        return match tokens.next() {
+            Some(token) => Err(ParserError::UnexpectedToken(token, "SET, ADD or DELETE".to_string())),
+            None => Err(ParserError::UnexpectedEndOfInput)
+        };
+    }
+
+    let condition = parse_write_condition(tokens)?;
+
+    return Ok(Statement::UpdateItem {
+        store_name,
+        predicate,
+        assignments,
+        additions,
+        deletions,
+        upsert,
+        condition
+    });
+}
+
+fn parse_delete_item<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Statement> {
+    let entire_partition = take_keyword(tokens, Keyword::Partition);
+
+    let store_name = parse_store_name(tokens)?;
+
+    expect_where(tokens)?;
+    expect_left_curly_brace(tokens)?;
+
+    let predicate = parse_predicate(tokens)?;
+
+    expect_right_curly_brace(tokens)?;
+
+    let condition = match entire_partition {
+        true => None,
+        false => parse_write_condition(tokens)?
+    };
+
+    return Ok(Statement::DeleteItem {
+        store_name,
+        predicate,
+        entire_partition,
+        condition
+    });
+}
+
+fn parse_create_store<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Statement> {
+    expect_keyword(tokens, Keyword::Store, "STORE")?;
+
+    let store_name = parse_store_name(tokens)?;
+
+    expect_keyword(tokens, Keyword::Hash, "HASH")?;
+    expect_keyword(tokens, Keyword::Key, "KEY")?;
+
+    let hash_key = parse_key_definition(tokens)?;
+
+    let sort_key = match take_keyword(tokens, Keyword::Sort) {
+        true => {
+            expect_keyword(tokens, Keyword::Key, "KEY")?;
+
+            Some(parse_key_definition(tokens)?)
+        },
+        false => None
+    };
+
+    let schema = match take_keyword(tokens, Keyword::With) {
+        true => Some(parse_schema(tokens)?),
+        false => None
+    };
+
+    return Ok(Statement::CreateStore {
+        store_name,
+        hash_key,
+        sort_key,
+        schema
+    });
 }
 
-fn parse_comparisons<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Vec<Comparison>> {
-    let mut comparisons = Vec::new();
+fn parse_drop_store<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Statement> {
+    expect_keyword(tokens, Keyword::Store, "STORE")?;
+
+    let store_name = parse_store_name(tokens)?;
+
+    return Ok(Statement::DropStore { store_name });
+}
+
+// BEGIN, COMMIT and ROLLBACK may each be followed by TRANSACTION.
+fn parse_transaction_control<I: Iterator<Item = Token>>(
+    tokens: &mut PeekingCursor<I>,
+    statement: Statement
+) -> ParserResult<Statement> {
+    take_keyword(tokens, Keyword::Transaction);
+
+    return Ok(statement);
+}
+
+fn parse_key_definition<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<KeyDefinition> {
+    let name = parse_attribute_name(tokens)?;
+
+    // The key type may be left out, in which case the key is a string.
+    let key_type = match tokens.peek().and_then(keyword_of) {
+        Some(Keyword::Integer) => KeyType::Integer,
+        Some(Keyword::String) => KeyType::String,
+        Some(Keyword::Binary) => KeyType::Binary,
+        Some(Keyword::Timestamp) => KeyType::Timestamp,
+        Some(Keyword::Decimal) => KeyType::Decimal,
+        _ => {
+            return Ok(KeyDefinition {
+                name,
+                key_type: KeyType::String
+            })
+        },
+    };
+    tokens.next();
+
+    return Ok(KeyDefinition { name, key_type });
+}
+
+fn parse_schema<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Schema> {
+    let lenient = take_keyword(tokens, Keyword::Lenient);
+
+    expect_keyword(tokens, Keyword::Schema, "SCHEMA")?;
+    expect_left_curly_brace(tokens)?;
+
+    let mut attributes = Vec::new();
 
     loop {
         let name = parse_attribute_name(tokens)?;
-        let operator = parse_comparison_operator(tokens)?;
-        let value = parse_expression(tokens, 0)?;
 
-        comparisons.push(Comparison { name, operator, value });
+        expect_colon(tokens)?;
 
-        match tokens.peek() {
+        let attribute_type = parse_attribute_type(tokens)?;
+
+        let not_null = match take_keyword(tokens, Keyword::Not) {
+            true => {
+                expect_keyword(tokens, Keyword::Null, "NULL")?;
+                true
+            },
+            false => false
+        };
+
+        attributes.push(AttributeDefinition {
+            name,
+            attribute_type,
+            not_null
+        });
+
+        match tokens.next() {
             Some(token) => match token.token_type {
-                TokenType::Comma => {
-                    tokens.next();
-                },
-                _ => break
+                TokenType::Comma => (),
+                TokenType::RightCurlyBrace => break,
+                _ => return Err(ParserError::UnexpectedToken(token, ", or }".to_string()))
             },
             None => return Err(ParserError::UnexpectedEndOfInput)
         }
     }
 
-    return Ok(comparisons);
+    return Ok(Schema { attributes, lenient });
+}
+
+fn parse_attribute_type<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<AttributeType> {
+    let token = tokens.next().ok_or(ParserError::UnexpectedEndOfInput)?;
+
+    let attribute_type = match keyword_of(&token) {
+        Some(Keyword::Integer) => AttributeType::Integer,
+        Some(Keyword::Float) => AttributeType::Float,
+        Some(Keyword::Decimal) => AttributeType::Decimal,
+        Some(Keyword::String) if take_keyword(tokens, Keyword::Set) => AttributeType::StringSet,
+        Some(Keyword::String) => AttributeType::String,
+        Some(Keyword::Boolean) => AttributeType::Boolean,
+        Some(Keyword::Binary) if take_keyword(tokens, Keyword::Set) => AttributeType::BinarySet,
+        Some(Keyword::Binary) => AttributeType::Binary,
+        Some(Keyword::Timestamp) => AttributeType::Timestamp,
+        Some(Keyword::Duration) => AttributeType::Duration,
+        Some(Keyword::List) => AttributeType::List,
+        Some(Keyword::Map) => AttributeType::Map,
+        Some(Keyword::Number) => {
+            expect_keyword(tokens, Keyword::Set, "SET")?;
+            AttributeType::NumberSet
+        },
+        _ => return Err(ParserError::UnexpectedToken(token, "attribute type".to_string()))
+    };
+
+    return Ok(attribute_type);
+}
+
+fn parse_write_condition<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Option<WriteCondition>> {
+    if !take_keyword(tokens, Keyword::If) {
+        return Ok(None);
+    }
+
+    let condition = match tokens.peek().and_then(keyword_of) {
+        Some(Keyword::Exists) => {
+            tokens.next();
+            WriteCondition::Exists
+        },
+        Some(Keyword::Not) => {
+            tokens.next();
+
+            match take_keyword(tokens, Keyword::Exists) {
+                true => WriteCondition::NotExists,
+                false => WriteCondition::Predicate(parse_negated_predicate(tokens)?)
+            }
+        },
+        _ => WriteCondition::Predicate(parse_predicate(tokens)?)
+    };
+
+    return Ok(Some(condition));
 }
 
 fn parse_assignments<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Vec<Assignment>> {
     let mut assignments = Vec::new();
 
     loop {
-        let name = parse_attribute_name(tokens)?;
+        let path = parse_attribute_path(tokens)?;
 
         expect_colon(tokens)?;
 
         let value = parse_expression(tokens, 0)?;
 
-        assignments.push(Assignment { name, value });
+        assignments.push(Assignment { path, value });
 
         match tokens.peek() {
             Some(token) => match token.token_type {
@@ -97,36 +398,60 @@ fn parse_assignments<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -
     return Ok(assignments);
 }
 
-fn parse_store_name<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<String> {
-    return match tokens.next() {
-        Some(token) => match token.token_type {
-            TokenType::Identifier(name) => Ok(name),
-            TokenType::String(name) => Ok(name),
-            _ => Err(ParserError::UnexpectedToken(token, "store name".to_string()))
-        },
-        None => Err(ParserError::UnexpectedEndOfInput)
-    };
+// ADD and DELETE take actions of the form `path value`, separated by commas.
+fn parse_set_actions<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Vec<Assignment>> {
+    let mut actions = Vec::new();
+
+    loop {
+        let path = parse_attribute_path(tokens)?;
+        let value = parse_expression(tokens, 0)?;
+
+        actions.push(Assignment { path, value });
+
+        match tokens.peek() {
+            Some(token) if token.token_type == TokenType::Comma => {
+                tokens.next();
+            },
+            _ => break
+        }
+    }
+
+    return Ok(actions);
 }
 
-fn parse_attribute_name<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<String> {
-    return match tokens.next() {
-        Some(token) => match token.token_type {
-            TokenType::Identifier(name) => Ok(name),
-            TokenType::String(name) => Ok(name),
-            _ => Err(ParserError::UnexpectedToken(
-                token,
-                "attribute name, as a string or identifier".to_string()
-            ))
+fn parse_projection<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<Vec<AttributePath>> {
+    let mut projection = vec![parse_attribute_path(tokens)?];
+
+    while let Some(token) = tokens.peek() {
+        if token.token_type != TokenType::Comma {
+            break;
+        }
+
+        tokens.next();
+        projection.push(parse_attribute_path(tokens)?);
+    }
+
+    return Ok(projection);
+}
+
+fn parse_limit<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<usize> {
+    let token = tokens.next().ok_or(ParserError::UnexpectedEndOfInput)?;
+
+    return match &token.token_type {
+        TokenType::Integer(limit) => match usize::try_from(*limit) {
+            Ok(limit) if limit > 0 => Ok(limit),
+            _ => Err(ParserError::InvalidLimit(token))
         },
-        None => Err(ParserError::UnexpectedEndOfInput)
+        TokenType::Float(_) | TokenType::Decimal(_) => Err(ParserError::InvalidLimit(token)),
+        _ => Err(ParserError::UnexpectedToken(token, "limit".to_string()))
     };
 }
 
-fn parse_comparison_operator<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<ComparisonOperator> {
+fn parse_store_name<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<String> {
     return match tokens.next() {
-        Some(token) => match ComparisonOperator::try_from(&token) {
-            Ok(operator) => Ok(operator),
-            Err(_) => Err(ParserError::InvalidComparisonOperator(token))
+        Some(token) => match token.token_type {
+            TokenType::Identifier(name) | TokenType::QuotedIdentifier(name) | TokenType::String(name) => Ok(name),
+            _ => Err(ParserError::UnexpectedToken(token, "store name".to_string()))
         },
         None => Err(ParserError::UnexpectedEndOfInput)
     };
@@ -143,13 +468,7 @@ fn expect_colon<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> Par
 }
 
 fn expect_where<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<()> {
-    return match tokens.next() {
-        Some(token) => match token.token_type {
-            TokenType::Keyword(Keyword::Where) => Ok(()),
-            _ => Err(ParserError::UnexpectedToken(token, "WHERE".to_string()))
-        },
-        None => Err(ParserError::UnexpectedEndOfInput)
-    };
+    return expect_keyword(tokens, Keyword::Where, "WHERE");
 }
 
 fn expect_left_curly_brace<I: Iterator<Item = Token>>(tokens: &mut PeekingCursor<I>) -> ParserResult<()> {